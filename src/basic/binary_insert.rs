@@ -36,6 +36,10 @@ where F: FnMut(&T, &T) -> bool,
 /// > 由于空元素或单元素数组被认为是有序的, 且插入元素后依然是有序的,
 /// > 所以已经遍历插入完毕的部分总是有序的, 所以能够应用二分查找
 ///
+/// 比较的代价来自于计算键时, 见 [`sort_by_cached_key`]
+///
+/// [`sort_by_cached_key`]: crate::permutation::sort_by_cached_key
+///
 /// **is stable sort**
 /// # Example
 /// ```
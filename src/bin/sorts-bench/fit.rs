@@ -0,0 +1,169 @@
+//! 经验复杂度拟合
+//!
+//! > 将在几何增长的规模上测得的数据拟合到候选模型`c * f(n)`,
+//! > 在对数空间中拟合, 即`ln(y) - ln(f(n))`的均值为`ln(c)`,
+//! > 其标准差作为误差, 误差最小的模型即为最佳拟合
+
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Linear,
+    NLogN,
+    N125,
+    N15,
+    Quadratic,
+}
+
+impl Model {
+    pub const ALL: [Self; 5] = [
+        Self::Linear,
+        Self::NLogN,
+        Self::N125,
+        Self::N15,
+        Self::Quadratic,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Linear => "n",
+            Self::NLogN => "n log n",
+            Self::N125 => "n^1.25",
+            Self::N15 => "n^1.5",
+            Self::Quadratic => "n^2",
+        }
+    }
+
+    pub fn eval(self, n: f64) -> f64 {
+        match self {
+            Self::Linear => n,
+            Self::NLogN => n * n.log2().max(1.0),
+            Self::N125 => n.powf(1.25),
+            Self::N15 => n.powf(1.5),
+            Self::Quadratic => n * n,
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Which value to fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Compares,
+    Moves,
+    Time,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "compares" => Ok(Self::Compares),
+            "moves" => Ok(Self::Moves),
+            "time" => Ok(Self::Time),
+            _ => Err(format!("unknown metric `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Compares => "compares",
+            Self::Moves => "moves",
+            Self::Time => "time",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub model: Model,
+    /// `y ≈ constant * model(n)`
+    pub constant: f64,
+    /// Standard deviation of `ln(y / model(n))`
+    pub error: f64,
+}
+
+/// Fit `(n, y)` points to all models, best first
+///
+/// points with `y <= 0` are ignored, return empty if no point left
+pub fn fit(points: &[(f64, f64)]) -> Vec<Fit> {
+    let points = points.iter()
+        .filter(|&&(n, y)| n > 0.0 && y > 0.0)
+        .collect::<Vec<_>>();
+    if points.is_empty() { return vec![] }
+    let len = points.len() as f64;
+    let mut fits = Model::ALL.into_iter()
+        .map(|model| {
+            let logs = points.iter()
+                .map(|&&(n, y)| y.ln() - model.eval(n).ln())
+                .collect::<Vec<_>>();
+            let mean = logs.iter().sum::<f64>() / len;
+            let var = logs.iter().map(|l| (l - mean).powi(2)).sum::<f64>() / len;
+            Fit { model, constant: mean.exp(), error: var.sqrt() }
+        })
+        .collect::<Vec<_>>();
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// Slope of log-log linear regression, is empirical exponent
+pub fn exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logs = points.iter()
+        .filter(|&&(n, y)| n > 0.0 && y > 0.0)
+        .map(|&(n, y)| (n.ln(), y.ln()))
+        .collect::<Vec<_>>();
+    if logs.len() < 2 { return None }
+    let len = logs.len() as f64;
+    let mx = logs.iter().map(|p| p.0).sum::<f64>() / len;
+    let my = logs.iter().map(|p| p.1).sum::<f64>() / len;
+    let sxy = logs.iter().map(|&(x, y)| (x - mx) * (y - my)).sum::<f64>();
+    let sxx = logs.iter().map(|&(x, _)| (x - mx).powi(2)).sum::<f64>();
+    (sxx > 0.0).then(|| sxy / sxx)
+}
+
+/// Geometric sizes from `from` to `to` (inclusive)
+pub fn geometric(from: usize, to: usize, factor: f64) -> Vec<usize> {
+    let mut sizes = vec![];
+    let mut n = from.max(1) as f64;
+    while n <= to as f64 {
+        let size = n.round() as usize;
+        if sizes.last() != Some(&size) {
+            sizes.push(size);
+        }
+        n *= factor.max(1.01);
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_test() {
+        let sizes = geometric(16, 1 << 14, 2.0);
+        assert_eq!(sizes.len(), 11);
+        for model in Model::ALL {
+            let points = sizes.iter()
+                .map(|&n| (n as f64, 3.0 * model.eval(n as f64)))
+                .collect::<Vec<_>>();
+            let best = fit(&points)[0];
+            assert_eq!(best.model, model);
+            assert!((best.constant - 3.0).abs() < 1e-6, "{best:?}");
+            assert!(best.error < 1e-9, "{best:?}");
+        }
+        let points = sizes.iter()
+            .map(|&n| (n as f64, (n as f64).powf(1.3)))
+            .collect::<Vec<_>>();
+        assert!((exponent(&points).unwrap() - 1.3).abs() < 1e-9);
+        assert!(fit(&[(10.0, 0.0)]).is_empty());
+    }
+}
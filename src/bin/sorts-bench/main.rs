@@ -0,0 +1,237 @@
+//! Benchmark all sorts on different sizes and distributions
+//!
+//! > 对所有已注册的排序, 在不同规模与分布的数据上测量耗时(多次运行的中位数)、
+//! > 比较次数与移动次数, 并与`slice::sort`/`slice::sort_unstable`对比
+//! >
+//! > `fit`模式在几何增长的规模上测量, 并拟合到候选复杂度模型
+
+mod fit;
+mod measure;
+mod report;
+
+use std::{env, process::ExitCode, str::FromStr};
+
+use rand::{rngs::StdRng, SeedableRng};
+use sorts_rs::{datagen::Distribution, metrics};
+
+use fit::Metric;
+use measure::{Entry, Record};
+use report::{FitRecord, Format};
+
+const USAGE: &str = "\
+Usage: sorts-bench [OPTIONS]
+       sorts-bench fit [OPTIONS] [FIT OPTIONS]
+
+Options:
+    --sizes <N,..>           input sizes [default: 100,1000,10000]
+    --dists <NAME,..>        distributions [default: all]
+    --algos <NAME,..>        algorithms, full or short name [default: all]
+    --runs <N>               runs per measurement, report median [default: 5]
+    --quadratic-limit <N>    skip O(n^2) sorts when size larger [default: 10000]
+    --format <FORMAT>        csv, json or markdown [default: markdown]
+    --seed <N>               random seed [default: 0]
+    -h, --help               print help
+
+Fit Options:
+    --metric <METRIC>        compares, moves or time [default: compares]
+    --from <N>               smallest size [default: 64]
+    --to <N>                 largest size [default: 8192]
+    --factor <F>             size growth factor [default: 2]
+
+Distributions: random, sorted, reversed, nearly_sorted, few_unique,
+               organ_pipe, sawtooth, equal";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Bench,
+    Fit,
+}
+
+#[derive(Debug, Clone)]
+struct Options {
+    mode: Mode,
+    sizes: Vec<usize>,
+    dists: Vec<Distribution>,
+    algos: Option<Vec<String>>,
+    runs: usize,
+    quadratic_limit: usize,
+    format: Format,
+    seed: u64,
+    metric: Metric,
+    from: usize,
+    to: usize,
+    factor: f64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Bench,
+            sizes: vec![100, 1000, 10000],
+            dists: Distribution::ALL.into(),
+            algos: None,
+            runs: 5,
+            quadratic_limit: 10000,
+            format: Format::Markdown,
+            seed: 0,
+            metric: Metric::Compares,
+            from: 64,
+            to: 8192,
+            factor: 2.0,
+        }
+    }
+}
+
+fn parse_list<T: FromStr>(s: &str) -> Result<Vec<T>, String>
+where T::Err: ToString,
+{
+    s.split(',')
+        .map(|s| s.trim().parse().map_err(|e: T::Err| e.to_string()))
+        .collect()
+}
+
+fn parse_one<T: FromStr>(s: &str) -> Result<T, String>
+where T::Err: ToString,
+{
+    s.parse().map_err(|e: T::Err| format!("invalid value `{s}`: {}", e.to_string()))
+}
+
+/// Parse options, `Ok(None)` is help
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut opts = Options::default();
+    let mut args = args.peekable();
+    if args.next_if(|arg| arg == "fit").is_some() {
+        opts.mode = Mode::Fit;
+    }
+    let mut sizes = None;
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" { return Ok(None) }
+        let mut value = || args.next().ok_or(format!("missing value of `{arg}`"));
+        match &*arg {
+            "--sizes" => sizes = Some(parse_list(&value()?)?),
+            "--dists" => opts.dists = parse_list(&value()?)?,
+            "--algos" => opts.algos = Some(parse_list(&value()?)?),
+            "--runs" => opts.runs = parse_one(&value()?)?,
+            "--quadratic-limit" => opts.quadratic_limit = parse_one(&value()?)?,
+            "--format" => opts.format = parse_one(&value()?)?,
+            "--seed" => opts.seed = parse_one(&value()?)?,
+            "--metric" => opts.metric = parse_one(&value()?)?,
+            "--from" => opts.from = parse_one(&value()?)?,
+            "--to" => opts.to = parse_one(&value()?)?,
+            "--factor" => opts.factor = parse_one(&value()?)?,
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+    opts.sizes = match (sizes, opts.mode) {
+        (Some(sizes), _) => sizes,
+        (None, Mode::Bench) => opts.sizes,
+        (None, Mode::Fit) => fit::geometric(opts.from, opts.to, opts.factor),
+    };
+    Ok(Some(opts))
+}
+
+fn select_entries(algos: Option<&[String]>) -> Result<Vec<Entry>, String> {
+    let entries = measure::entries();
+    let Some(algos) = algos else { return Ok(entries) };
+    algos.iter()
+        .map(|name| {
+            entries.iter().copied()
+                .find(|entry| entry.matches(name))
+                .ok_or(format!("unknown algorithm `{name}`"))
+        })
+        .collect()
+}
+
+fn run(opts: &Options) -> Result<Vec<Record>, String> {
+    let entries = select_entries(opts.algos.as_deref())?;
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let mut records = vec![];
+    for &dist in &opts.dists {
+        for &size in &opts.sizes {
+            let input = dist.generate(size, &mut rng);
+            let inversions = metrics::inversions(&input, u64::lt);
+            for entry in &entries {
+                if entry.quadratic && size > opts.quadratic_limit { continue }
+                if !entry.applicable(&input) { continue }
+                eprintln!("running {} on {dist} n={size}", entry.name);
+                records.push(Record {
+                    algo: entry.name,
+                    dist,
+                    size,
+                    inversions,
+                    median: entry.median_time(&input, opts.runs),
+                    counts: entry.count(&input),
+                });
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn fit_point(entry: &Entry, input: &[u64], opts: &Options) -> Option<f64> {
+    match opts.metric {
+        Metric::Compares => entry.count(input).map(|c| c.compares as f64),
+        Metric::Moves => entry.count(input).map(|c| c.moves as f64),
+        Metric::Time => Some(entry.median_time(input, opts.runs).as_nanos() as f64),
+    }
+}
+
+fn run_fit(opts: &Options) -> Result<Vec<FitRecord>, String> {
+    let entries = select_entries(opts.algos.as_deref())?;
+    let mut rng = StdRng::seed_from_u64(opts.seed);
+    let mut records = vec![];
+    for &dist in &opts.dists {
+        let inputs = opts.sizes.iter()
+            .map(|&size| dist.generate(size, &mut rng))
+            .collect::<Vec<_>>();
+        for entry in &entries {
+            eprintln!("fitting {} on {dist}", entry.name);
+            let points = inputs.iter()
+                .filter(|input| !entry.quadratic || input.len() <= opts.quadratic_limit)
+                .filter(|input| entry.applicable(input))
+                .filter_map(|input| {
+                    Some((input.len() as f64, fit_point(entry, input, opts)?))
+                })
+                .collect::<Vec<_>>();
+            if points.is_empty() { continue }
+            records.push(FitRecord {
+                algo: entry.name,
+                dist,
+                metric: opts.metric,
+                fits: fit::fit(&points),
+                exponent: fit::exponent(&points),
+            });
+        }
+    }
+    Ok(records)
+}
+
+fn main() -> ExitCode {
+    let opts = match parse_args(env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+    let output = match opts.mode {
+        Mode::Bench => run(&opts)
+            .map(|records| report::render(opts.format, &records)),
+        Mode::Fit => run_fit(&opts)
+            .map(|records| report::render_fit(opts.format, &records)),
+    };
+    match output {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
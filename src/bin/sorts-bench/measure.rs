@@ -0,0 +1,238 @@
+//! 运行排序并测量耗时、比较次数与移动次数
+
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+use sorts_rs::{
+    datagen::Distribution,
+    other,
+    registry::{self, LtSortFn},
+};
+
+/// Element which records its address when last seen
+///
+/// 元素的移动无法被直接观测, 所以在每次比较以及排序结束时检查元素地址,
+/// 地址改变即记为一次移动, 这是实际移动次数的下界
+#[derive(Debug, Clone, Default)]
+pub struct Tracked {
+    val: u64,
+    seen: Cell<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Tracker {
+    compares: Cell<u64>,
+    moves: Cell<u64>,
+}
+impl Tracker {
+    fn observe(&self, ele: &Tracked) {
+        let addr = ele as *const Tracked as usize;
+        let seen = ele.seen.replace(addr);
+        if seen != 0 && seen != addr {
+            self.moves.set(self.moves.get() + 1);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub compares: u64,
+    pub moves: u64,
+}
+
+/// 使用返回 [`Ordering`] 的比较函数的排序, 如 [`slice::sort_by`]
+type CmpSortFn<T> = fn(&mut [T], &mut dyn FnMut(&T, &T) -> Ordering);
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Lt {
+        plain: LtSortFn<u64>,
+        tracked: LtSortFn<Tracked>,
+    },
+    /// 每次比较直接得到 [`Ordering`], 按一次比较计数,
+    /// 而不是由`lt`推导 (那样每次比较最多需要调用两次`lt`)
+    Cmp {
+        plain: CmpSortFn<u64>,
+        tracked: CmpSortFn<Tracked>,
+    },
+    Key {
+        sort: fn(&mut [u64]),
+        applicable: fn(&[u64]) -> bool,
+    },
+}
+
+/// A sort can be benchmarked
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub name: &'static str,
+    pub quadratic: bool,
+    kind: Kind,
+}
+
+/// Registered sorts, [`slice::sort`] and [`slice::sort_unstable`] as baseline,
+/// and integer sorts
+pub fn entries() -> Vec<Entry> {
+    let mut entries = vec![
+        Entry {
+            name: "std::sort",
+            quadratic: false,
+            kind: Kind::Cmp {
+                plain: |arr, cmp| arr.sort_by(cmp),
+                tracked: |arr, cmp| arr.sort_by(cmp),
+            },
+        },
+        Entry {
+            name: "std::sort_unstable",
+            quadratic: false,
+            kind: Kind::Cmp {
+                plain: |arr, cmp| arr.sort_unstable_by(cmp),
+                tracked: |arr, cmp| arr.sort_unstable_by(cmp),
+            },
+        },
+    ];
+    let algos = registry::lt_sorts::<u64>().into_iter()
+        .zip(registry::lt_sorts::<Tracked>());
+    for (plain, tracked) in algos {
+        entries.push(Entry {
+            name: plain.name,
+            quadratic: plain.quadratic,
+            kind: Kind::Lt { plain: plain.sort, tracked: tracked.sort },
+        });
+    }
+    entries.push(Entry {
+        name: "other::radix_sort",
+        quadratic: false,
+        kind: Kind::Key {
+            sort: |arr| other::radix_sort(arr),
+            applicable: |_| true,
+        },
+    });
+    entries.push(Entry {
+        name: "other::radix_sort_radix::<8>",
+        quadratic: false,
+        kind: Kind::Key {
+            sort: |arr| other::radix_sort_radix::<8, _>(arr),
+            applicable: |_| true,
+        },
+    });
+    entries.push(Entry {
+        name: "other::msd_radix_sort",
+        quadratic: false,
+        kind: Kind::Key {
+            sort: |arr| other::msd_radix_sort(arr),
+            applicable: |_| true,
+        },
+    });
+    entries.push(Entry {
+        name: "other::american_flag_sort",
+        quadratic: false,
+        kind: Kind::Key {
+            sort: |arr| other::american_flag_sort(arr),
+            applicable: |_| true,
+        },
+    });
+    entries.push(Entry {
+        name: "other::count_sort",
+        quadratic: false,
+        kind: Kind::Key {
+            sort: |arr| other::count_sort(arr),
+            applicable: |arr| {
+                let (min, max) = (arr.iter().min(), arr.iter().max());
+                min.zip(max).is_none_or(|(min, max)| max - min < 1 << 24)
+            },
+        },
+    });
+    entries
+}
+
+impl Entry {
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.name.split_once("::").map(|(_, short)| short) == Some(name)
+    }
+
+    pub fn applicable(&self, input: &[u64]) -> bool {
+        match self.kind {
+            Kind::Lt { .. } | Kind::Cmp { .. } => true,
+            Kind::Key { applicable, .. } => applicable(input),
+        }
+    }
+
+    /// Run once on a copy of `input`, return elapsed time
+    pub fn time(&self, input: &[u64]) -> Duration {
+        let mut arr = input.to_vec();
+        let start = Instant::now();
+        match self.kind {
+            Kind::Lt { plain, .. } => plain(&mut arr, &mut u64::lt),
+            Kind::Cmp { plain, .. } => plain(&mut arr, &mut u64::cmp),
+            Kind::Key { sort, .. } => sort(&mut arr),
+        }
+        let elapsed = start.elapsed();
+        debug_assert!(arr.is_sorted(), "{} failed", self.name);
+        elapsed
+    }
+
+    /// Median time of `runs` times
+    pub fn median_time(&self, input: &[u64], runs: usize) -> Duration {
+        let mut times = (0..runs.max(1))
+            .map(|_| self.time(input))
+            .collect::<Vec<_>>();
+        times.sort();
+        times[times.len() >> 1]
+    }
+
+    /// Count compares and moves, integer sorts not supported
+    pub fn count(&self, input: &[u64]) -> Option<Counts> {
+        let tracker = Tracker::default();
+        let mut arr = input.iter()
+            .map(|&val| Tracked { val, seen: Cell::new(0) })
+            .collect::<Vec<_>>();
+        arr.iter().for_each(|ele| tracker.observe(ele));
+        let mut compare = |a: &Tracked, b: &Tracked| {
+            tracker.compares.set(tracker.compares.get() + 1);
+            tracker.observe(a);
+            tracker.observe(b);
+            a.val.cmp(&b.val)
+        };
+        match self.kind {
+            Kind::Lt { tracked, .. } => tracked(&mut arr, &mut |a, b| compare(a, b).is_lt()),
+            Kind::Cmp { tracked, .. } => tracked(&mut arr, &mut compare),
+            Kind::Key { .. } => return None,
+        }
+        arr.iter().for_each(|ele| tracker.observe(ele));
+        Some(Counts {
+            compares: tracker.compares.get(),
+            moves: tracker.moves.get(),
+        })
+    }
+}
+
+/// Result of an entry on an input
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub algo: &'static str,
+    pub dist: Distribution,
+    pub size: usize,
+    /// 输入的逆序对数, 用于观察耗时与无序程度的关系
+    pub inversions: usize,
+    pub median: Duration,
+    pub counts: Option<Counts>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 有序的输入上, 标准库的排序只需要`n - 1`次比较, 每次比较只计数一次
+    #[test]
+    fn std_compares_test() {
+        let input = (0..200).collect::<Vec<u64>>();
+        for name in ["std::sort", "std::sort_unstable"] {
+            let entry = entries().into_iter().find(|e| e.name == name).unwrap();
+            let counts = entry.count(&input).unwrap();
+            assert_eq!(counts, Counts { compares: 199, moves: 0 }, "{name}");
+        }
+    }
+}
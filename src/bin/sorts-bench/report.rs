@@ -0,0 +1,197 @@
+//! 将测量结果输出为 CSV, JSON 或 Markdown 表格
+
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use sorts_rs::datagen::Distribution;
+
+use crate::{fit::{Fit, Metric}, measure::Record};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("unknown format `{s}`")),
+        }
+    }
+}
+
+fn opt(n: Option<u64>) -> String {
+    n.map(|n| n.to_string()).unwrap_or_default()
+}
+
+fn human_time(time: Duration) -> String {
+    let ns = time.as_nanos() as f64;
+    match ns {
+        ns if ns < 1e3 => format!("{ns:.0}ns"),
+        ns if ns < 1e6 => format!("{:.2}µs", ns / 1e3),
+        ns if ns < 1e9 => format!("{:.2}ms", ns / 1e6),
+        ns => format!("{:.2}s", ns / 1e9),
+    }
+}
+
+pub fn csv(records: &[Record]) -> String {
+    let mut out = String::from("algorithm,distribution,size,inversions,median_ns,compares,moves\n");
+    for rec in records {
+        writeln!(out, "{},{},{},{},{},{},{}",
+            rec.algo,
+            rec.dist,
+            rec.size,
+            rec.inversions,
+            rec.median.as_nanos(),
+            opt(rec.counts.map(|c| c.compares)),
+            opt(rec.counts.map(|c| c.moves)),
+        ).unwrap();
+    }
+    out
+}
+
+pub fn json(records: &[Record]) -> String {
+    let json_opt = |n: Option<u64>| {
+        n.map(|n| n.to_string()).unwrap_or_else(|| "null".into())
+    };
+    let mut out = String::from("[\n");
+    for (i, rec) in records.iter().enumerate() {
+        let sep = if i + 1 == records.len() { "" } else { "," };
+        writeln!(out, "  {{\"algorithm\": \"{}\", \"distribution\": \"{}\", \
+            \"size\": {}, \"inversions\": {}, \"median_ns\": {}, \"compares\": {}, \"moves\": {}}}{sep}",
+            rec.algo,
+            rec.dist,
+            rec.size,
+            rec.inversions,
+            rec.median.as_nanos(),
+            json_opt(rec.counts.map(|c| c.compares)),
+            json_opt(rec.counts.map(|c| c.moves)),
+        ).unwrap();
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Markdown table, `vs std::sort` is time ratio to [`slice::sort`] on same input
+pub fn markdown(records: &[Record]) -> String {
+    let mut out = String::from(
+        "| algorithm | distribution | n | inversions | median time | vs std::sort | compares | moves |\n\
+        |---|---|--:|--:|--:|--:|--:|--:|\n");
+    for rec in records {
+        let base = records.iter()
+            .find(|base| base.algo == "std::sort"
+                && (base.dist, base.size) == (rec.dist, rec.size));
+        let ratio = base
+            .map(|base| {
+                let base = base.median.as_secs_f64().max(f64::MIN_POSITIVE);
+                format!("{:.2}x", rec.median.as_secs_f64() / base)
+            })
+            .unwrap_or_default();
+        writeln!(out, "| `{}` | {} | {} | {} | {} | {ratio} | {} | {} |",
+            rec.algo,
+            rec.dist,
+            rec.size,
+            rec.inversions,
+            human_time(rec.median),
+            opt(rec.counts.map(|c| c.compares)),
+            opt(rec.counts.map(|c| c.moves)),
+        ).unwrap();
+    }
+    out
+}
+
+/// Fitted result of an entry on a distribution
+#[derive(Debug, Clone)]
+pub struct FitRecord {
+    pub algo: &'static str,
+    pub dist: Distribution,
+    pub metric: Metric,
+    /// Best first
+    pub fits: Vec<Fit>,
+    pub exponent: Option<f64>,
+}
+
+fn fit_fields(rec: &FitRecord) -> [String; 5] {
+    let best = rec.fits.first();
+    [
+        best.map(|fit| fit.model.to_string()).unwrap_or_default(),
+        best.map(|fit| format!("{:.4}", fit.constant)).unwrap_or_default(),
+        best.map(|fit| format!("{:.4}", fit.error)).unwrap_or_default(),
+        rec.exponent.map(|e| format!("{e:.3}")).unwrap_or_default(),
+        rec.fits.get(1).map(|fit| fit.model.to_string()).unwrap_or_default(),
+    ]
+}
+
+pub fn fit_csv(records: &[FitRecord]) -> String {
+    let mut out = String::from(
+        "algorithm,distribution,metric,best_model,constant,error,exponent,second_model\n");
+    for rec in records {
+        writeln!(out, "{},{},{},{}",
+            rec.algo,
+            rec.dist,
+            rec.metric,
+            fit_fields(rec).join(","),
+        ).unwrap();
+    }
+    out
+}
+
+pub fn fit_json(records: &[FitRecord]) -> String {
+    let mut out = String::from("[\n");
+    for (i, rec) in records.iter().enumerate() {
+        let sep = if i + 1 == records.len() { "" } else { "," };
+        let null_or = |s: String, f: fn(String) -> String| {
+            if s.is_empty() { "null".into() } else { f(s) }
+        };
+        let [best, constant, error, exponent, second] = fit_fields(rec);
+        let [best, second] = [best, second].map(|s| null_or(s, |s| format!("\"{s}\"")));
+        let [constant, error, exponent] = [constant, error, exponent].map(|s| null_or(s, |s| s));
+        writeln!(out, "  {{\"algorithm\": \"{}\", \"distribution\": \"{}\", \
+            \"metric\": \"{}\", \"best_model\": {best}, \"constant\": {constant}, \
+            \"error\": {error}, \"exponent\": {exponent}, \"second_model\": {second}}}{sep}",
+            rec.algo,
+            rec.dist,
+            rec.metric,
+        ).unwrap();
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Markdown table, `exponent` is slope of log-log regression
+pub fn fit_markdown(records: &[FitRecord]) -> String {
+    let mut out = String::from(
+        "| algorithm | distribution | metric | best model | constant | error | exponent | second |\n\
+        |---|---|---|---|--:|--:|--:|---|\n");
+    for rec in records {
+        let [best, constant, error, exponent, second] = fit_fields(rec);
+        writeln!(out, "| `{}` | {} | {} | {best} | {constant} | {error} | {exponent} | {second} |",
+            rec.algo,
+            rec.dist,
+            rec.metric,
+        ).unwrap();
+    }
+    out
+}
+
+pub fn render_fit(format: Format, records: &[FitRecord]) -> String {
+    match format {
+        Format::Csv => fit_csv(records),
+        Format::Json => fit_json(records),
+        Format::Markdown => fit_markdown(records),
+    }
+}
+
+pub fn render(format: Format, records: &[Record]) -> String {
+    match format {
+        Format::Csv => csv(records),
+        Format::Json => json(records),
+        Format::Markdown => markdown(records),
+    }
+}
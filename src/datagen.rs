@@ -0,0 +1,120 @@
+//! 测试数据生成, 提供多种不同分布的输入
+//!
+//! 不同的排序在不同分布下的表现差异很大, 如插入排序在基本有序的数据中非常快,
+//! 而简单快速排序在有序数据中将退化至`O(n^2)`
+
+use std::{fmt, str::FromStr};
+
+use rand::Rng;
+
+/// Input distribution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Distribution {
+    /// 均匀随机
+    Random,
+    /// 升序
+    Sorted,
+    /// 降序
+    Reversed,
+    /// 升序, 但是随机交换了约`n/20`对元素
+    NearlySorted,
+    /// 只有约`sqrt(n)`种不同的值
+    FewUnique,
+    /// 先升序后降序
+    OrganPipe,
+    /// 多段长度约为`sqrt(n)`的升序段
+    Sawtooth,
+    /// 所有元素相等
+    Equal,
+}
+
+impl Distribution {
+    pub const ALL: [Self; 8] = [
+        Self::Random,
+        Self::Sorted,
+        Self::Reversed,
+        Self::NearlySorted,
+        Self::FewUnique,
+        Self::OrganPipe,
+        Self::Sawtooth,
+        Self::Equal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Sorted => "sorted",
+            Self::Reversed => "reversed",
+            Self::NearlySorted => "nearly_sorted",
+            Self::FewUnique => "few_unique",
+            Self::OrganPipe => "organ_pipe",
+            Self::Sawtooth => "sawtooth",
+            Self::Equal => "equal",
+        }
+    }
+
+    /// Generate `len` values
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::datagen::Distribution;
+    /// let mut rng = rand::thread_rng();
+    /// assert_eq!(Distribution::Sorted.generate(4, &mut rng), [0, 1, 2, 3]);
+    /// assert_eq!(Distribution::OrganPipe.generate(5, &mut rng), [0, 1, 2, 1, 0]);
+    /// assert_eq!(Distribution::Random.generate(100, &mut rng).len(), 100);
+    /// ```
+    pub fn generate(self, len: usize, rng: &mut impl Rng) -> Vec<u64> {
+        let n = len as u64;
+        let sqrt = (len as f64).sqrt().ceil().max(1.0) as u64;
+        match self {
+            Self::Random => (0..len).map(|_| rng.gen()).collect(),
+            Self::Sorted => (0..n).collect(),
+            Self::Reversed => (0..n).rev().collect(),
+            Self::NearlySorted => {
+                let mut arr = (0..n).collect::<Vec<_>>();
+                for _ in 0..len / 20 {
+                    let (i, j) = (rng.gen_range(0..len), rng.gen_range(0..len));
+                    arr.swap(i, j);
+                }
+                arr
+            },
+            Self::FewUnique => (0..len).map(|_| rng.gen_range(0..sqrt)).collect(),
+            Self::OrganPipe => (0..n).map(|i| i.min(n - 1 - i)).collect(),
+            Self::Sawtooth => (0..n).map(|i| i % sqrt).collect(),
+            Self::Equal => vec![0; len],
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDistribution(pub String);
+
+impl fmt::Display for UnknownDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown distribution `{}`", self.0)
+    }
+}
+
+impl std::error::Error for UnknownDistribution { }
+
+impl FromStr for Distribution {
+    type Err = UnknownDistribution;
+
+    /// # Example
+    /// ```
+    /// # use sorts_rs::datagen::Distribution;
+    /// assert_eq!("few_unique".parse(), Ok(Distribution::FewUnique));
+    /// assert!("foo".parse::<Distribution>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|dist| dist.name() == s)
+            .ok_or_else(|| UnknownDistribution(s.into()))
+    }
+}
@@ -2,6 +2,12 @@ pub mod utils;
 pub mod basic;
 pub mod normal;
 pub mod other;
+pub mod select;
+pub mod metrics;
+pub mod smart;
+pub mod permutation;
+pub mod registry;
+pub mod datagen;
 
 #[cfg(test)]
 mod tests;
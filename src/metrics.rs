@@ -0,0 +1,271 @@
+//! 有序程度的度量 (presortedness), 描述输入距离有序有多远
+//!
+//! > 所有度量都在`lt`下计算, 相等的元素之间视为有序,
+//! > 有序的输入上除 [`runs`] 为`1`外均为`0`.
+//! > 自适应的排序 (如 [`insert_sort`] 的复杂度为`O(n + inversions)`)
+//! > 在这些度量较小的输入上更快
+//!
+//! [`insert_sort`]: crate::basic::insert_sort
+
+use crate::permutation::argsort;
+
+/// Is `arr` sorted under `lt`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::is_sorted;
+/// assert!(is_sorted(&[1, 1, 2, 3], i32::lt));
+/// assert!(!is_sorted(&[1, 3, 2], i32::lt));
+/// assert!(is_sorted(&[3, 2, 1], i32::gt));
+/// ```
+pub fn is_sorted<T, F>(arr: &[T], mut lt: F) -> bool
+where F: FnMut(&T, &T) -> bool,
+{
+    arr.windows(2).all(|w| !lt(&w[1], &w[0]))
+}
+
+fn inversions_inner<T, F>(
+    arr: &[T],
+    idx: &mut [usize],
+    buf: &mut Vec<usize>,
+    lt: &mut F,
+) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    if idx.len() < 2 { return 0 }
+    let mid = idx.len() >> 1;
+    let mut count = inversions_inner(arr, &mut idx[..mid], buf, lt)
+        + inversions_inner(arr, &mut idx[mid..], buf, lt);
+
+    buf.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < idx.len() {
+        if lt(&arr[idx[j]], &arr[idx[i]]) {
+            // 左侧剩余的元素都大于 idx[j]
+            count += mid - i;
+            buf.push(idx[j]);
+            j += 1;
+        } else {
+            buf.push(idx[i]);
+            i += 1;
+        }
+    }
+    buf.extend_from_slice(&idx[i..mid]);
+    buf.extend_from_slice(&idx[j..]);
+    idx.copy_from_slice(buf);
+    count
+}
+
+/// Number of inversions
+///
+/// > 逆序对的数量, 即`i < j`且`arr[j] < arr[i]`的对数,
+/// > 也是 [`bubble_sort`] 与 [`insert_sort`] 需要的交换次数.
+/// > 在下标上归并排序并在归并时计数, 复杂度`O(n*log(n))`, 不修改`arr`
+///
+/// [`bubble_sort`]: crate::basic::bubble_sort
+/// [`insert_sort`]: crate::basic::insert_sort
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::inversions;
+/// assert_eq!(inversions(&[1, 2, 3], i32::lt), 0);
+/// assert_eq!(inversions(&[2, 3, 1], i32::lt), 2);
+/// assert_eq!(inversions(&[3, 2, 1], i32::lt), 3);
+/// assert_eq!(inversions(&[2, 2, 2], i32::lt), 0);
+/// ```
+pub fn inversions<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    let mut idx = (0..arr.len()).collect::<Vec<_>>();
+    let mut buf = Vec::with_capacity(arr.len());
+    inversions_inner(arr, &mut idx, &mut buf, &mut lt)
+}
+
+/// Number of non-descending runs
+///
+/// > 极长不下降段的数量, 空数组为`0`, 有序时为`1`,
+/// > 自然归并排序需要`log(runs)`轮归并
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::runs;
+/// assert_eq!(runs::<i32, _>(&[], i32::lt), 0);
+/// assert_eq!(runs(&[1, 2, 2, 3], i32::lt), 1);
+/// assert_eq!(runs(&[1, 3, 2, 4, 0], i32::lt), 3);
+/// ```
+pub fn runs<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    if arr.is_empty() { return 0 }
+    1 + arr.windows(2).filter(|w| lt(&w[1], &w[0])).count()
+}
+
+/// Rem, minimum number of elements to remove to be sorted
+///
+/// > 删除最少多少个元素使剩余的有序, 即`n`减去最长不下降子序列的长度,
+/// > 使用耐心排序的方法, 复杂度`O(n*log(n))`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::rem;
+/// assert_eq!(rem(&[1, 2, 3], i32::lt), 0);
+/// assert_eq!(rem(&[1, 9, 2, 3, 0], i32::lt), 2);
+/// assert_eq!(rem(&[3, 2, 1], i32::lt), 2);
+/// ```
+pub fn rem<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    // tails[l]: 长度为 l+1 的不下降子序列中, 末尾最小的那个的下标
+    let mut tails: Vec<usize> = vec![];
+    for (i, ele) in arr.iter().enumerate() {
+        let pos = tails.partition_point(|&t| !lt(ele, &arr[t]));
+        match tails.get_mut(pos) {
+            Some(tail) => *tail = i,
+            None => tails.push(i),
+        }
+    }
+    arr.len() - tails.len()
+}
+
+/// 排序后的名次, 相等的元素名次相同, 名次从`0`开始连续
+fn ranks<T, F>(arr: &[T], lt: &mut F) -> Vec<usize>
+where F: FnMut(&T, &T) -> bool,
+{
+    let idx = argsort(arr, &mut *lt);
+    let mut ranks = vec![0; arr.len()];
+    for k in 1..idx.len() {
+        let (prev, cur) = (idx[k-1], idx[k]);
+        ranks[cur] = ranks[prev] + usize::from(lt(&arr[prev], &arr[cur]));
+    }
+    ranks
+}
+
+/// Osc, oscillation
+///
+/// > 对每个元素, 计算严格跨过它的相邻对的数量, 即`min(arr[j], arr[j+1]) < x < max(arr[j], arr[j+1])`,
+/// > 再对所有元素求和, 描述数据来回振荡的程度.
+/// > 按名次计数, 复杂度`O(n*log(n))`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::osc;
+/// assert_eq!(osc(&[1, 2, 3, 4], i32::lt), 0);
+/// // 2 与 3 各被 (1, 4), (4, 0) 跨过, 1 被 (4, 0), (0, 2) 跨过
+/// assert_eq!(osc(&[1, 4, 0, 2, 3], i32::lt), 6);
+/// ```
+pub fn osc<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    let ranks = ranks(arr, &mut lt);
+    // lows[r]: 下端名次为 r 的相邻对数, highs[r]: 上端名次为 r 的相邻对数
+    let mut lows = vec![0; arr.len() + 1];
+    let mut highs = vec![0; arr.len() + 1];
+    for w in ranks.windows(2) {
+        let (lo, hi) = (w[0].min(w[1]), w[0].max(w[1]));
+        if lo != hi {
+            lows[lo] += 1;
+            highs[hi] += 1;
+        }
+    }
+
+    // 跨过名次 r 的对数 = 下端 < r 的对数 - 上端 <= r 的对数
+    let mut crosses = vec![0; arr.len() + 1];
+    let (mut below, mut ended) = (0, 0);
+    for r in 0..=arr.len() {
+        ended += highs[r];
+        crosses[r] = below - ended;
+        below += lows[r];
+    }
+    ranks.iter().map(|&r| crosses[r]).sum()
+}
+
+/// Max, the max displacement
+///
+/// > 元素在`arr`中的位置与在稳定排序后的位置之差的最大值,
+/// > 例如每个元素离最终位置都不超过`k`时, 插入排序的复杂度为`O(n*k)`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::max_displacement;
+/// assert_eq!(max_displacement(&[1, 1, 2], i32::lt), 0);
+/// assert_eq!(max_displacement(&[2, 1, 4, 3], i32::lt), 1);
+/// assert_eq!(max_displacement(&[9, 1, 2, 3], i32::lt), 3);
+/// ```
+pub fn max_displacement<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    argsort(arr, &mut lt).into_iter()
+        .enumerate()
+        .map(|(k, i)| k.abs_diff(i))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Ham, number of elements not in their sorted place
+///
+/// > 与排序后的结果相比, 不相等的位置的数量
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::ham;
+/// assert_eq!(ham(&[1, 2, 2, 3], i32::lt), 0);
+/// assert_eq!(ham(&[3, 2, 1], i32::lt), 2);
+/// assert_eq!(ham(&[2, 3, 4, 1], i32::lt), 4);
+/// ```
+pub fn ham<T, F>(arr: &[T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    let idx = argsort(arr, &mut lt);
+    idx.into_iter()
+        .enumerate()
+        .filter(|&(k, i)| lt(&arr[k], &arr[i]) || lt(&arr[i], &arr[k]))
+        .count()
+}
+
+/// All presortedness metrics of an input, see [`analyze`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Metrics {
+    pub len: usize,
+    pub is_sorted: bool,
+    pub inversions: usize,
+    pub runs: usize,
+    pub rem: usize,
+    pub osc: usize,
+    pub max_displacement: usize,
+    pub ham: usize,
+}
+
+impl Metrics {
+    /// 逆序对数占最大可能值`n*(n-1)/2`的比例, 有序为`0`, 逆序为`1`
+    pub fn disorder(&self) -> f64 {
+        let pairs = self.len * self.len.saturating_sub(1) / 2;
+        if pairs == 0 { return 0.0 }
+        self.inversions as f64 / pairs as f64
+    }
+}
+
+/// Compute all metrics
+///
+/// # Example
+/// ```
+/// # use sorts_rs::metrics::analyze;
+/// let m = analyze(&[2, 1, 3, 5, 4], i32::lt);
+/// assert!(!m.is_sorted);
+/// assert_eq!((m.inversions, m.runs, m.rem), (2, 3, 2));
+/// assert_eq!((m.max_displacement, m.ham), (1, 4));
+/// assert_eq!(m.disorder(), 0.2);
+/// ```
+pub fn analyze<T, F>(arr: &[T], mut lt: F) -> Metrics
+where F: FnMut(&T, &T) -> bool,
+{
+    Metrics {
+        len: arr.len(),
+        is_sorted: is_sorted(arr, &mut lt),
+        inversions: inversions(arr, &mut lt),
+        runs: runs(arr, &mut lt),
+        rem: rem(arr, &mut lt),
+        osc: osc(arr, &mut lt),
+        max_displacement: max_displacement(arr, &mut lt),
+        ham: ham(arr, &mut lt),
+    }
+}
@@ -1,12 +1,14 @@
+use std::mem::take;
+
 use crate::cmp;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Error {
+pub(crate) enum Error {
     IndexOutOfRange(usize),
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct BHeap<'a, T>(pub &'a mut [T]);
+pub(crate) struct BHeap<'a, T>(pub &'a mut [T]);
 impl<'a, T> BHeap<'a, T> {
     fn left_i(&self, idx: usize) -> usize {
         (idx << 1) + 1
@@ -28,7 +30,7 @@ impl<'a, T> BHeap<'a, T> {
         self.0.swap(a, b)
     }
 
-    fn len(&self) -> usize {
+    pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
 
@@ -46,7 +48,7 @@ impl<'a, T> BHeap<'a, T> {
             })
     }
 
-    fn filter_down<F>(
+    pub(crate) fn filter_down<F>(
         &mut self,
         idx: usize,
         mut lt: F,
@@ -62,19 +64,39 @@ impl<'a, T> BHeap<'a, T> {
         Ok(())
     }
 
-    fn pop_to<F>(&'a mut self, i: impl IntoIterator, mut lt: F)
+    /// 将`idx`处的元素向上移动, 直到不大于其父节点
+    pub(crate) fn filter_up<F>(&mut self, mut idx: usize, mut lt: F)
+    where F: FnMut(&T, &T) -> bool,
+    {
+        while idx > 0 {
+            let parent = (idx - 1) >> 1;
+            if !cmp!(lt(self.0[parent],< self.0[idx])) { break }
+            self.swap(parent, idx);
+            idx = parent;
+        }
+    }
+
+    /// 将堆顶移至末尾并移出堆, 返回它
+    pub(crate) fn pop<F>(&mut self, lt: F) -> Option<&'a mut T>
+    where F: FnMut(&T, &T) -> bool,
+    {
+        if self.len() == 0 { return None }
+        self.swap(0, self.len() - 1);
+        let (last, rest) = take(&mut self.0).split_last_mut().unwrap();
+        self.0 = rest;
+        self.filter_down(0, lt).unwrap();
+        Some(last)
+    }
+
+    pub(crate) fn pop_to<F>(&mut self, i: impl IntoIterator, mut lt: F)
     where F: FnMut(&T, &T) -> bool,
     {
         for _ in i {
-            if self.len() == 0 { break }
-            self.swap(0, self.len() - 1);
-            let (_, rest) = self.0.split_last_mut().unwrap();
-            self.0 = rest;
-            self.filter_down(0, &mut lt).unwrap();
+            if self.pop(&mut lt).is_none() { break }
         }
     }
 
-    fn make<F>(&mut self, mut lt: F)
+    pub(crate) fn make<F>(&mut self, mut lt: F)
     where F: FnMut(&T, &T) -> bool,
     {
         if self.len() < 2 { return }
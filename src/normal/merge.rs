@@ -1,13 +1,43 @@
-use std::{iter::{self, zip}, mem::take};
+use std::{iter::zip, mem::take, ops::Range};
 
 use crate::{cmp, i};
 
+/// 双向归并的状态, 尚未写回的元素在`buf`中, 而`arr`中有相同数量的空位
+///
+/// - forward: `buf[i..]`将写入`arr[k..j]`
+/// - backward: `buf[..i]`将写入`arr[j..k]`
+///
+/// drop 时将剩余元素写回空位, 这同时是归并的收尾,
+/// 也保证了比较函数 panic 时不会丢失元素
+struct MergeState<'a, T: Default> {
+    arr: &'a mut [T],
+    buf: &'a mut Vec<T>,
+    forward: bool,
+    i: usize,
+    j: usize,
+    k: usize,
+}
+impl<T: Default> Drop for MergeState<'_, T> {
+    fn drop(&mut self) {
+        if self.forward {
+            for ele in &mut self.buf[self.i..] {
+                self.arr[i!(self.k++)] = take(ele);
+            }
+        } else {
+            for ele in self.buf[..self.i].iter_mut().rev() {
+                self.arr[i!(--self.k)] = take(ele);
+            }
+        }
+    }
+}
+
 /// Merge sorted `arr[..len]` and `arr[len..]`
 ///
 /// > 优化了辅助空间使用的双向归并算法, 最差空间复杂度`O(n/2)`
 ///
 /// 涉及到元素的双重存在, 需要使用unsafe且通过guard保证其UnwindSafe,
-/// 这过于复杂, 虽然有能力实现但并不好阅读, 所以将约束Default并使用take
+/// 这过于复杂, 虽然有能力实现但并不好阅读, 所以将约束Default并使用take,
+/// 再由guard在结束或panic时将`buf`中剩余元素写回
 ///
 /// # Examples
 /// ```
@@ -51,38 +81,91 @@ where F: FnMut(&T, &T) -> bool,
     if len <= rest {
         // forward
         buf.extend(arr[..len].iter_mut().map(take));
-        let mut i = buf.iter_mut()
-            .map(take)
-            .peekable();
-        let mut j = len;
-        let mut k = 0;
-        while let (Some(a), Some(b)) = (i.peek(), arr.get(j)) {
-            if cmp!(lt(a,<= b)) {
-                arr[i!(k++)] = i.next().unwrap();
+        let mut st = MergeState { arr, buf, forward: true, i: 0, j: len, k: 0 };
+        while st.i < st.buf.len() && st.j < st.arr.len() {
+            let (a, b) = (&st.buf[st.i], &st.arr[st.j]);
+            let ele = if cmp!(lt(a,<= b)) {
+                take(&mut st.buf[i!(st.i++)])
             } else {
-                arr[i!(k++)] = take(&mut arr[i!(j++)]);
-            }
-        }
-        for ele in i {
-            arr[i!(k++)] = ele;
+                take(&mut st.arr[i!(st.j++)])
+            };
+            st.arr[i!(st.k++)] = ele;
         }
     } else {
         // backward
         buf.extend(arr[len..].iter_mut().map(take));
-        let mut i = iter::from_fn(|| buf.pop())
-            .peekable();
-        let mut j = len;
-        let mut k = arr.len();
-        while i.peek().is_some() && j > 0 {
-            let (a, b) = (i.peek().unwrap(), &arr[j-1]);
-            if cmp!(lt(a,>= b)) {
-                arr[i!(--k)] = i.next().unwrap();
+        let (i, k) = (buf.len(), arr.len());
+        let mut st = MergeState { arr, buf, forward: false, i, j: len, k };
+        while st.i > 0 && st.j > 0 {
+            let (a, b) = (&st.buf[st.i-1], &st.arr[st.j-1]);
+            let ele = if cmp!(lt(a,>= b)) {
+                take(&mut st.buf[i!(--st.i)])
             } else {
-                arr[i!(--k)] = take(&mut arr[i!(--j)]);
-            }
+                take(&mut st.arr[i!(--st.j)])
+            };
+            st.arr[i!(--st.k)] = ele;
+        }
+    }
+}
+
+/// 单向归并的状态, `buf`中是已经归并的元素,
+/// 它们来自于`arr[..i]`和`arr[len..j]`中的空位
+///
+/// drop 时按顺序将`buf`写回这些空位, 归并完成时空位就是整个`arr`
+pub(crate) struct NormalMergeState<'a, T> {
+    arr: &'a mut [T],
+    buf: &'a mut Vec<T>,
+    len: usize,
+    i: usize,
+    j: usize,
+}
+impl<'a, T: Default> NormalMergeState<'a, T> {
+    /// 开始归并`arr[..len]`和`arr[len..]`, `buf`将被清空
+    pub(crate) fn new(arr: &'a mut [T], buf: &'a mut Vec<T>, len: usize) -> Self {
+        buf.clear();
+        Self { arr, buf, len, i: 0, j: len }
+    }
+
+    /// 两侧的下一个元素, 任意一侧已经用尽时为`None`
+    pub(crate) fn heads(&self) -> Option<(&T, &T)> {
+        if self.i < self.len && self.j < self.arr.len() {
+            Some((&self.arr[self.i], &self.arr[self.j]))
+        } else {
+            None
         }
-        for ele in i {
-            arr[i!(--k)] = ele;
+    }
+
+    /// 两侧的下一个元素在`arr`中的下标
+    pub(crate) fn pos(&self) -> (usize, usize) {
+        (self.i, self.j)
+    }
+
+    /// 输出左侧或右侧的下一个元素
+    pub(crate) fn take(&mut self, left: bool) {
+        let ele = if left {
+            take(&mut self.arr[i!(self.i++)])
+        } else {
+            take(&mut self.arr[i!(self.j++)])
+        };
+        self.buf.push(ele);
+    }
+
+    /// 依次输出两侧剩余的元素并写回`arr`, 返回两侧剩余的下标范围
+    pub(crate) fn finish(mut self) -> (Range<usize>, Range<usize>) {
+        let (lo, hi) = (self.i..self.len, self.j..self.arr.len());
+        self.buf.extend(self.arr[lo.clone()].iter_mut().map(take));
+        self.buf.extend(self.arr[hi.clone()].iter_mut().map(take));
+        (self.i, self.j) = (self.len, self.arr.len());
+        (lo, hi)
+    }
+}
+impl<T> Drop for NormalMergeState<'_, T> {
+    fn drop(&mut self) {
+        let (lo, hi) = self.arr.split_at_mut(self.len);
+        let holes = lo[..self.i].iter_mut()
+            .chain(&mut hi[..self.j-self.len]);
+        for (dst, src) in zip(holes, self.buf.drain(..)) {
+            *dst = src;
         }
     }
 }
@@ -128,24 +211,12 @@ pub fn normal_merge<T, F>(
 where F: FnMut(&T, &T) -> bool,
       T: Default,
 {
-    buf.clear();
-    let (lo, hi) = arr.split_at_mut(len);
-    let (mut lo, mut hi) = (
-        lo.iter_mut().map(take).peekable(),
-        hi.iter_mut().map(take).peekable(),
-    );
-    while let (Some(a), Some(b)) = (lo.peek(), hi.peek()) {
-        if cmp!(lt(a,<= b)) {
-            buf.push(lo.next().unwrap());
-        } else {
-            buf.push(hi.next().unwrap());
-        }
-    }
-    buf.extend(lo);
-    buf.extend(hi);
-    for (dst, src) in zip(arr, buf.drain(..)) {
-        *dst = src;
+    let mut st = NormalMergeState::new(arr, buf, len);
+    while let Some((a, b)) = st.heads() {
+        let left = cmp!(lt(a,<= b));
+        st.take(left);
     }
+    st.finish();
 }
 
 fn merge_sort_with_buf_inner<T, F>(
@@ -1,9 +1,12 @@
 use crate::cmp;
 
-fn quick_sort_inner<T, F>(arr: &mut [T], lt: &mut F)
+/// 以最后一个元素为基准划分, 返回基准最终的位置`p`,
+/// `arr[..p]`均不大于基准, `arr[p+1..]`均大于基准
+///
+/// `arr`不能为空
+pub(crate) fn partition<T, F>(arr: &mut [T], lt: &mut F) -> usize
 where F: FnMut(&T, &T) -> bool,
 {
-    if arr.len() < 2 { return }
     let last = arr.len()-1;
     let (mut l, mut r) = (0, last);
     while l < r {
@@ -14,9 +17,17 @@ where F: FnMut(&T, &T) -> bool,
             l += 1;
         }
     }
-    arr.swap(l, arr.len()-1);
-    quick_sort_inner(&mut arr[..l], lt);
-    quick_sort_inner(&mut arr[r+1..], lt);
+    arr.swap(l, last);
+    l
+}
+
+fn quick_sort_inner<T, F>(arr: &mut [T], lt: &mut F)
+where F: FnMut(&T, &T) -> bool,
+{
+    if arr.len() < 2 { return }
+    let p = partition(arr, lt);
+    quick_sort_inner(&mut arr[..p], lt);
+    quick_sort_inner(&mut arr[p+1..], lt);
 }
 
 /// Quick sort
@@ -56,7 +56,7 @@ pub fn sedgewick_expr(i: usize) -> usize {
     if i & 1 == 0 {
         9 * (b4pow(u) - (1<<u)) + 1
     } else {
-        b4pow(u+2) - 3 * (1<<u+2) + 1
+        b4pow(u+2) - 3 * (1<<(u+2)) + 1
     }
 }
 
@@ -0,0 +1,81 @@
+use crate::{basic::insert_sort, cmp};
+
+use super::network::OPTIMAL;
+
+/// Max length of [`sort_array`]
+pub const SORT_ARRAY_MAX: usize = 32;
+
+struct ArraySort<const N: usize>;
+impl<const N: usize> ArraySort<N> {
+    /// 在编译期选择策略, 长度不超过 16 时使用已知的最优排序网络
+    const NETWORK: Option<&'static [&'static [(u8, u8)]]> = {
+        assert!(N <= SORT_ARRAY_MAX, "sort_array only support N <= 32");
+        if N < OPTIMAL.len() { Some(OPTIMAL[N]) } else { None }
+    };
+}
+
+/// Sort fixed-size array
+///
+/// > 对于较小的定长数组, 根据长度在编译期选择排序方法,
+/// > 长度不超过 16 时使用已知比较器最少的排序网络
+/// > (同 [`SortingNetwork::optimal`]), 否则使用插入排序,
+/// > 由于长度是常量, 编译器可以将其完全展开
+///
+/// 长度超过 [`SORT_ARRAY_MAX`] 时将无法编译
+///
+/// [`SortingNetwork::optimal`]: super::SortingNetwork::optimal
+///
+/// # Examples
+/// ```
+/// # use sorts_rs::other::sort_array;
+/// let mut scores = [7, 3, 9, 1, 8, 2, 6, 5];
+/// sort_array(&mut scores, i32::lt);
+/// assert_eq!(scores, [1, 2, 3, 5, 6, 7, 8, 9]);
+/// ```
+/// median of 5
+/// ```
+/// # use sorts_rs::other::sort_array;
+/// let mut arr = [2.5, 0.1, 9.0, 4.2, 3.3];
+/// sort_array(&mut arr, f64::lt);
+/// assert_eq!(arr[2], 3.3);
+/// ```
+/// too long
+/// ```compile_fail
+/// # use sorts_rs::other::sort_array;
+/// let mut arr = [0; 33];
+/// sort_array(&mut arr, i32::lt);
+/// ```
+pub fn sort_array<const N: usize, T, F>(arr: &mut [T; N], mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    match ArraySort::<N>::NETWORK {
+        Some(layers) => {
+            for &(i, j) in layers.iter().copied().flatten() {
+                let (i, j) = (i.into(), j.into());
+                if cmp!(lt(arr[i],> arr[j])) {
+                    arr.swap(i, j)
+                }
+            }
+        },
+        None => insert_sort(arr, lt),
+    }
+}
+
+#[test]
+fn sort_array_test() {
+    fn check<const N: usize>() {
+        for _ in 0..50 {
+            let mut arr: [usize; N] = std::array::from_fn(|_| {
+                rand::random::<usize>() % (N/2 + 1)
+            });
+            let mut expected = arr;
+            expected.sort();
+            sort_array(&mut arr, usize::lt);
+            assert_eq!(arr, expected, "N {N}");
+        }
+    }
+    macro_rules! check {
+        ($($n:literal)*) => { $(check::<$n>();)* };
+    }
+    check!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+}
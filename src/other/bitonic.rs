@@ -1,3 +1,5 @@
+use std::{iter::zip, mem};
+
 use crate::cmp;
 
 fn assert_bin_exp(n: usize) {
@@ -81,7 +83,9 @@ where F: FnMut(&T, &T) -> bool,
 /// > 这么做的好处是可以通过简单暴力的巨量并行来换取效率,
 /// > 在特殊情况下可以用GPU来进行高效排序
 /// >
-/// > 目前实现使用单线程实现
+/// > 目前实现使用单线程实现, 多线程版本见 [`par_bitonic_sort`](super::par_bitonic_sort)
+///
+/// 任意长度的版本见 [`bitonic_sort_any`]
 ///
 /// # Panics
 /// `arr.len() != 0 && !arr.len().is_power_of_two()`
@@ -92,6 +96,73 @@ where F: FnMut(&T, &T) -> bool,
     bitonic_sort_unchecked(arr, lt)
 }
 
+/// Compare and swap, make `a` and `b` ordered (or reverse ordered)
+pub(super) fn compare_swap<T, F>(a: &mut T, b: &mut T, lt: &mut F, rev: bool)
+where F: FnMut(&T, &T) -> bool,
+{
+    let swap = if rev {
+        cmp!(lt(a,< b))
+    } else {
+        cmp!(lt(a,> b))
+    };
+    if swap {
+        mem::swap(a, b)
+    }
+}
+
+/// 小于长度的最大的 2^n, 任意长度双调归并的分割点
+pub(super) fn bitonic_any_mid(len: usize) -> usize {
+    1 << (len-1).ilog2()
+}
+
+pub(super) fn bitonic_merge_any_inner<T, F>(arr: &mut [T], lt: &mut F, rev: bool)
+where F: FnMut(&T, &T) -> bool,
+{
+    if arr.len() < 2 { return }
+    let mid = bitonic_any_mid(arr.len());
+
+    let (lo, hi) = arr.split_at_mut(mid);
+    for (a, b) in zip(lo, hi) {
+        compare_swap(a, b, lt, rev)
+    }
+
+    bitonic_merge_any_inner(&mut arr[..mid], lt, rev);
+    bitonic_merge_any_inner(&mut arr[mid..], lt, rev);
+}
+
+pub(super) fn bitonic_sort_any_inner<T, F>(arr: &mut [T], lt: &mut F, rev: bool)
+where F: FnMut(&T, &T) -> bool,
+{
+    if arr.len() < 2 { return }
+    let mid = arr.len() >> 1;
+
+    bitonic_sort_any_inner(&mut arr[..mid], lt, !rev);
+    bitonic_sort_any_inner(&mut arr[mid..], lt, rev);
+    bitonic_merge_any_inner(arr, lt, rev);
+}
+
+/// Bitonic Sort, support any length
+///
+/// > 支持任意长度的双调排序, 将数组分为`floor(n/2)`和`ceil(n/2)`两半,
+/// > 分别以相反方向排序, 归并时将`i`与`i+m`比较交换(`m`为小于`n`的最大的`2^k`),
+/// > 再分别归并`[..m]`与`[m..]`
+/// >
+/// > 比较顺序依然只和长度有关, 与输入数据无关, 在长度为`2^k`时等同于 [`bitonic_sort`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::bitonic_sort_any;
+/// let lt = i32::lt;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6];
+/// bitonic_sort_any(&mut arr, lt);
+/// assert_eq!(arr, [0, 1, 2, 3, 5, 6, 9]);
+/// ```
+pub fn bitonic_sort_any<T, F>(arr: &mut [T], mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    bitonic_sort_any_inner(arr, &mut lt, false)
+}
+
 #[test]
 fn assert_2pow_test() {
     let nums = [
@@ -143,3 +214,23 @@ fn bitonic_merge_test() {
         assert_eq!(x, y);
     }
 }
+
+#[test]
+fn bitonic_sort_any_test() {
+    for len in 0..=1000 {
+        let mut x = (0..len).map(|i| (i * 7919 + len) % 97).collect::<Vec<_>>();
+        let mut y = x.clone();
+        bitonic_sort_any(&mut x, usize::lt);
+        y.sort();
+        assert_eq!(x, y, "len {len}");
+    }
+
+    // 0-1 principle, check all binary inputs
+    for len in 0..=12 {
+        for bits in 0..1u32 << len {
+            let mut x = (0..len).map(|i| bits >> i & 1).collect::<Vec<_>>();
+            bitonic_sort_any(&mut x, u32::lt);
+            assert!(x.is_sorted(), "len {len} bits {bits:b}");
+        }
+    }
+}
@@ -1,14 +1,18 @@
-use std::fmt::Debug;
+use std::ops::Range;
 
 use crate::utils::IterMinMax;
 
+use super::{RadixKey, RadixUint};
+
 /// Counting sort, like [`count_sort`], but use extern buffer
 ///
 /// **is new value sort**
 ///
+/// 通过 [`RadixKey`] 计算下标, 即`key(ele) - key(base)`
+///
 /// # Panics
-/// - `max(arr) - base >= buf.len()`
-/// - `max(arr) - base < 0`
+/// - `key(max(arr)) - key(base) >= buf.len()`
+/// - `max(arr) < base`
 ///
 /// # Examples
 /// ```
@@ -26,22 +30,32 @@ use crate::utils::IterMinMax;
 /// count_sort_with_buf(&mut arr, 1, &mut buf);
 /// assert_eq!(arr, [1, 2, 3, 4, 5, 6, 9]);
 /// ```
+/// negative numbers
+/// ```
+/// # use sorts_rs::other::count_sort_with_buf;
+/// let mut arr = [3, -1, 5, -2, 0];
+/// let mut buf = [0; 8];
+/// count_sort_with_buf(&mut arr, -2, &mut buf);
+/// assert_eq!(arr, [-2, -1, 0, 3, 5]);
+/// ```
 pub fn count_sort_with_buf<T>(
     mut arr: &mut [T],
-    base: usize,
+    base: T,
     buf: &mut [usize],
 )
-where T: TryInto<usize> + TryFrom<usize> + Copy + Debug,
-      <T as TryInto<usize>>::Error: Debug,
-      <T as TryFrom<usize>>::Error: Debug,
+where T: RadixKey,
 {
+    let base = base.to_radix_key().to_u128();
     buf.fill(0);
     for &mut ele in &mut *arr {
-        buf[ele.try_into().unwrap() - base] += 1;
+        let idx = ele.to_radix_key().to_u128().checked_sub(base)
+            .expect("value less than base");
+        buf[usize::try_from(idx).unwrap_or(usize::MAX)] += 1;
     }
     for (i, count) in buf.iter().copied().enumerate() {
+        if count == 0 { continue }
         let (fill, rest) = arr.split_at_mut(count);
-        fill.fill((i + base).try_into().unwrap());
+        fill.fill(T::from_radix_key(RadixUint::from_u128(base + i as u128)));
         arr = rest;
     }
 }
@@ -52,7 +66,7 @@ where T: TryInto<usize> + TryFrom<usize> + Copy + Debug,
 /// > 然后再通过字典顺序按计数数量生成值.
 /// > 这个算法的复杂度是`O(n)`, 但是需要`max-min+1`的辅助空间
 ///
-/// **is new value sort**
+/// **is new value sort**, 按键移动元素本身见 [`counting_sort_by_key`]
 ///
 /// Alloc buffer, and set base index
 ///
@@ -62,17 +76,94 @@ where T: TryInto<usize> + TryFrom<usize> + Copy + Debug,
 /// let mut arr = [3, 1, 5, 2, 9, 6, 4];
 /// count_sort(&mut arr);
 /// assert_eq!(arr, [1, 2, 3, 4, 5, 6, 9]);
+///
+/// let mut arr = ['c', 'a', 'd', 'a'];
+/// count_sort(&mut arr);
+/// assert_eq!(arr, ['a', 'a', 'c', 'd']);
 /// ```
+///
+/// # Panics
+/// 键的范围超出`usize`
 pub fn count_sort<T>(arr: &mut [T])
-where T: TryInto<usize> + TryFrom<usize> + Copy + Debug,
-      <T as TryInto<usize>>::Error: Debug,
-      <T as TryFrom<usize>>::Error: Debug,
+where T: RadixKey,
 {
     if arr.len() < 2 { return }
     let (base, max) = arr.iter()
-        .map(|&n| <T as TryInto<usize>>::try_into(n).unwrap())
+        .map(|&n| n.to_radix_key())
         .minmax()
         .unwrap();
-    let mut buf = vec![0; max - base + 1];
-    count_sort_with_buf(arr, base, &mut buf)
+    let len = usize::try_from(max.to_u128() - base.to_u128()).ok()
+        .and_then(|len| len.checked_add(1))
+        .expect("key range too large");
+    let mut buf = vec![0; len];
+    count_sort_with_buf(arr, T::from_radix_key(base), &mut buf)
+}
+
+/// Move `arr[i]` to `arr[dest[i]]`, `dest` must be a permutation of `0..n`
+///
+/// 沿着置换环交换, 结束后`dest`将变为`0..n`
+pub(super) fn permute<T>(arr: &mut [T], dest: &mut [usize]) {
+    debug_assert_eq!(arr.len(), dest.len());
+    for i in 0..arr.len() {
+        while dest[i] != i {
+            let j = dest[i];
+            arr.swap(i, j);
+            dest.swap(i, j);
+        }
+    }
+}
+
+/// Counting sort by key, stable
+///
+/// > 按键计数排序, 先对每个键计数, 通过前缀和得到每个键的起始位置,
+/// > 再将整个元素移动到对应的位置, 键相同的元素保持原有顺序
+/// >
+/// > 与 [`count_sort`] 不同, 这会移动元素本身而不是生成新的值,
+/// > 需要`O(n + range.len())`的辅助空间
+///
+/// # Panics
+/// `key(ele)`不在`range`中
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::counting_sort_by_key;
+/// #[derive(Debug, PartialEq)]
+/// struct Event { time: u64, name: &'static str }
+///
+/// let mut events = [
+///     Event { time: 3725, name: "a" },
+///     Event { time: 120, name: "b" },
+///     Event { time: 3601, name: "c" },
+///     Event { time: 7300, name: "d" },
+///     Event { time: 59, name: "e" },
+/// ];
+/// // by hour
+/// counting_sort_by_key(&mut events, 0..24, |e| (e.time / 3600) as usize);
+/// let names = events.map(|e| e.name);
+/// assert_eq!(names, ["b", "e", "a", "c", "d"]);
+/// ```
+pub fn counting_sort_by_key<T, F>(arr: &mut [T], range: Range<usize>, mut key: F)
+where F: FnMut(&T) -> usize,
+{
+    let mut dest = arr.iter()
+        .map(|ele| {
+            let k = key(ele);
+            assert!(range.contains(&k), "key {k} out of range {range:?}");
+            k - range.start
+        })
+        .collect::<Vec<_>>();
+
+    let mut counts = vec![0; range.len()];
+    for &k in &dest {
+        counts[k] += 1;
+    }
+    let mut sum = 0;
+    for cur in &mut counts {
+        (*cur, sum) = (sum, sum + *cur);
+    }
+    for k in &mut dest {
+        let pos = &mut counts[*k];
+        (*k, *pos) = (*pos, *pos + 1);
+    }
+    permute(arr, &mut dest)
 }
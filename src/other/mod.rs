@@ -5,13 +5,32 @@
 //!   如计数排序的复杂度就是`O(n)`
 //!
 //! - 有特殊要求的排序, 如双调排序.
+//!
+//! - 针对字符串的排序, 如多键快速排序, 利用字符串的公共前缀减少比较
+//!
+//! - 排序的应用, 如后缀数组的构造
 
+mod radix_key;
 mod count;
 mod radix;
 mod backet;
 mod bitonic;
+mod par_bitonic;
+mod network;
+mod odd_even;
+mod array;
+mod strings;
+mod suffix;
 
+pub use radix_key::*;
 pub use count::*;
 pub use radix::*;
+#[allow(unused_imports)]
 pub use backet::*;
 pub use bitonic::*;
+pub use par_bitonic::*;
+pub use network::*;
+pub use odd_even::*;
+pub use array::*;
+pub use strings::*;
+pub use suffix::*;
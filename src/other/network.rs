@@ -0,0 +1,466 @@
+use std::fmt;
+
+use crate::cmp;
+
+use super::odd_even::{odd_even_merge_pairs, odd_even_transposition_pairs};
+
+/// Layers of best known sorting networks, index is length
+///
+/// 已知比较器数量最少的排序网络, 长度`0..=16`,
+/// 其中 14 与 15 由 16 的网络 (Green) 去掉最高的线得到
+pub(super) const OPTIMAL: [&[&[(u8, u8)]]; 17] = [
+    &[],
+    &[],
+    &[&[(0,1)]],
+    &[&[(0,2)],&[(0,1)],&[(1,2)]],
+    &[&[(0,2),(1,3)],&[(0,1),(2,3)],&[(1,2)]],
+    &[&[(0,3),(1,4)],&[(0,2),(1,3)],&[(0,1),(2,4)],&[(1,2),(3,4)],&[(2,3)]],
+    &[&[(0,5),(1,3),(2,4)],&[(1,2),(3,4)],&[(0,3),(2,5)],&[(0,1),(2,3),(4,5)],&[(1,2),(3,4)]],
+    &[&[(0,6),(2,3),(4,5)],&[(0,2),(1,4),(3,6)],&[(0,1),(2,5),(3,4)],&[(1,2),(4,6)],&[(2,3),(4,5)],&[(1,2),(3,4),(5,6)]],
+    &[&[(0,2),(1,3),(4,6),(5,7)],&[(0,4),(1,5),(2,6),(3,7)],&[(0,1),(2,3),(4,5),(6,7)],&[(2,4),(3,5)],&[(1,4),(3,6)],&[(1,2),(3,4),(5,6)]],
+    &[&[(0,3),(1,7),(2,5),(4,8)],&[(0,7),(2,4),(3,8),(5,6)],&[(0,2),(1,3),(4,5),(7,8)],&[(1,4),(3,6),(5,7)],&[(0,1),(2,4),(3,5),(6,8)],&[(2,3),(4,5),(6,7)],&[(1,2),(3,4),(5,6)]],
+    &[&[(0,8),(1,9),(2,7),(3,5),(4,6)],&[(0,2),(1,4),(5,8),(7,9)],&[(0,3),(2,4),(5,7),(6,9)],&[(0,1),(3,6),(8,9)],&[(1,5),(2,3),(4,8),(6,7)],&[(1,2),(3,5),(4,6),(7,8)],&[(2,3),(4,5),(6,7)],&[(3,4),(5,6)]],
+    &[&[(0,9),(1,6),(2,4),(3,7),(5,8)],&[(0,1),(3,5),(4,10),(6,9),(7,8)],&[(1,3),(2,5),(4,7),(8,10)],&[(0,4),(1,2),(3,7),(5,9),(6,8)],&[(0,1),(2,6),(4,5),(7,8),(9,10)],&[(2,4),(3,6),(5,7),(8,9)],&[(1,2),(3,4),(5,6),(7,8)],&[(2,3),(4,5),(6,7)]],
+    &[&[(0,8),(1,7),(2,6),(3,11),(4,10),(5,9)],&[(0,1),(2,5),(3,4),(6,9),(7,8),(10,11)],&[(0,2),(1,6),(5,10),(9,11)],&[(0,3),(1,2),(4,6),(5,7),(8,11),(9,10)],&[(1,4),(3,5),(6,8),(7,10)],&[(1,3),(2,5),(6,9),(8,10)],&[(2,3),(4,5),(6,7),(8,9)],&[(4,6),(5,7)],&[(3,4),(5,6),(7,8)]],
+    &[&[(0,12),(1,10),(2,9),(3,7),(5,11),(6,8)],&[(1,6),(2,3),(4,11),(7,9),(8,10)],&[(0,4),(1,2),(3,6),(7,8),(9,10),(11,12)],&[(4,6),(5,9),(8,11),(10,12)],&[(0,5),(3,8),(4,7),(6,11),(9,10)],&[(0,1),(2,5),(6,9),(7,8),(10,11)],&[(1,3),(2,4),(5,6),(9,10)],&[(1,2),(3,4),(5,7),(6,8)],&[(2,3),(4,5),(6,7),(8,9)],&[(3,4),(5,6)]],
+    &[&[(0,13),(1,12),(4,8),(5,6),(7,11),(9,10)],&[(0,5),(1,7),(2,9),(3,4),(6,13),(11,12)],&[(0,1),(2,3),(4,5),(6,8),(7,9),(10,11),(12,13)],&[(0,2),(1,3),(4,10),(5,11),(6,7),(8,9)],&[(1,2),(3,12),(4,6),(5,7),(8,10),(9,11)],&[(1,4),(2,6),(5,8),(7,10),(9,13)],&[(2,4),(3,6),(9,12),(11,13)],&[(3,5),(6,8),(7,9),(10,12)],&[(3,4),(5,6),(7,8),(9,10),(11,12)],&[(6,7),(8,9)]],
+    &[&[(0,13),(1,12),(3,14),(4,8),(5,6),(7,11),(9,10)],&[(0,5),(1,7),(2,9),(3,4),(6,13),(8,14),(11,12)],&[(0,1),(2,3),(4,5),(6,8),(7,9),(10,11),(12,13)],&[(0,2),(1,3),(4,10),(5,11),(6,7),(8,9),(12,14)],&[(1,2),(3,12),(4,6),(5,7),(8,10),(9,11),(13,14)],&[(1,4),(2,6),(5,8),(7,10),(9,13),(11,14)],&[(2,4),(3,6),(9,12),(11,13)],&[(3,5),(6,8),(7,9),(10,12)],&[(3,4),(5,6),(7,8),(9,10),(11,12)],&[(6,7),(8,9)]],
+    &[&[(0,13),(1,12),(2,15),(3,14),(4,8),(5,6),(7,11),(9,10)],&[(0,5),(1,7),(2,9),(3,4),(6,13),(8,14),(10,15),(11,12)],&[(0,1),(2,3),(4,5),(6,8),(7,9),(10,11),(12,13),(14,15)],&[(0,2),(1,3),(4,10),(5,11),(6,7),(8,9),(12,14),(13,15)],&[(1,2),(3,12),(4,6),(5,7),(8,10),(9,11),(13,14)],&[(1,4),(2,6),(5,8),(7,10),(9,13),(11,14)],&[(2,4),(3,6),(9,12),(11,13)],&[(3,5),(6,8),(7,9),(10,12)],&[(3,4),(5,6),(7,8),(9,10),(11,12)],&[(6,7),(8,9)]],
+];
+
+/// Invalid comparator in [`SortingNetwork`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NetworkError {
+    /// Index out of network length
+    IndexOutOfRange(usize),
+    /// Comparator `(i, i)`
+    SameIndex(usize),
+    /// Index used more than once in a layer
+    Conflict(usize),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOutOfRange(i) => write!(f, "index {i} out of range"),
+            Self::SameIndex(i) => write!(f, "comparator ({i}, {i}) compares same index"),
+            Self::Conflict(i) => write!(f, "index {i} used more than once in a layer"),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError { }
+
+/// Comparator network, is layers of compare-exchange pairs
+///
+/// > 比较网络, 由若干层比较器组成, 同一层的比较器互不相关, 可以并行执行.
+/// > 比较器`(i, j)`会使得`arr[i] <= arr[j]`, 其中`i > j`也是允许的,
+/// > 它表示一个反向的比较器, 例如双调排序中降序的部分
+///
+/// 比较顺序只和网络有关, 与输入数据无关
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::SortingNetwork;
+/// let net = SortingNetwork::from_pairs(3, [(0, 1), (1, 2), (0, 1)]).unwrap();
+/// assert_eq!((net.size(), net.depth()), (3, 3));
+/// assert!(net.verify().is_ok());
+///
+/// let mut arr = [3, 1, 2];
+/// net.apply(&mut arr, i32::lt);
+/// assert_eq!(arr, [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SortingNetwork {
+    len: usize,
+    layers: Vec<Vec<(usize, usize)>>,
+}
+
+impl SortingNetwork {
+    /// Empty network of `len` wires
+    pub fn new(len: usize) -> Self {
+        Self { len, layers: vec![] }
+    }
+
+    /// Build from layers, check every layer is valid
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::{SortingNetwork, NetworkError};
+    /// let net = SortingNetwork::from_layers(2, vec![vec![(0, 1)]]);
+    /// assert!(net.is_ok());
+    /// let net = SortingNetwork::from_layers(3, vec![vec![(0, 1), (1, 2)]]);
+    /// assert_eq!(net, Err(NetworkError::Conflict(1)));
+    /// let net = SortingNetwork::from_layers(2, vec![vec![(0, 2)]]);
+    /// assert_eq!(net, Err(NetworkError::IndexOutOfRange(2)));
+    /// ```
+    pub fn from_layers(
+        len: usize,
+        layers: Vec<Vec<(usize, usize)>>,
+    ) -> Result<Self, NetworkError> {
+        let mut used = vec![usize::MAX; len];
+        for (depth, layer) in layers.iter().enumerate() {
+            for &(i, j) in layer {
+                if i == j { return Err(NetworkError::SameIndex(i)) }
+                for idx in [i, j] {
+                    let used = used.get_mut(idx)
+                        .ok_or(NetworkError::IndexOutOfRange(idx))?;
+                    if *used == depth { return Err(NetworkError::Conflict(idx)) }
+                    *used = depth;
+                }
+            }
+        }
+        let layers = layers.into_iter()
+            .filter(|layer| !layer.is_empty())
+            .collect();
+        Ok(Self { len, layers })
+    }
+
+    /// Build from sequential comparators,
+    /// each comparator is put into the earliest possible layer
+    pub fn from_pairs(
+        len: usize,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Result<Self, NetworkError> {
+        let mut net = Self::new(len);
+        for (i, j) in pairs {
+            net.push(i, j)?;
+        }
+        Ok(net)
+    }
+
+    /// Append a comparator, put it into the earliest possible layer
+    pub fn push(&mut self, i: usize, j: usize) -> Result<(), NetworkError> {
+        if i == j { return Err(NetworkError::SameIndex(i)) }
+        if let Some(&idx) = [i, j].iter().find(|&&idx| idx >= self.len) {
+            return Err(NetworkError::IndexOutOfRange(idx));
+        }
+        let depth = self.layers.iter()
+            .rposition(|layer| {
+                layer.iter().any(|&(a, b)| [a, b].iter().any(|x| [i, j].contains(x)))
+            })
+            .map_or(0, |d| d+1);
+        if depth == self.layers.len() {
+            self.layers.push(vec![]);
+        }
+        self.layers[depth].push((i, j));
+        Ok(())
+    }
+
+    /// Best known network (fewest comparators) for `len <= 16`
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::optimal(16).unwrap();
+    /// assert_eq!((net.size(), net.depth()), (60, 10));
+    /// assert!(SortingNetwork::optimal(17).is_none());
+    /// ```
+    pub fn optimal(len: usize) -> Option<Self> {
+        let layers = OPTIMAL.get(len)?.iter()
+            .map(|layer| {
+                layer.iter()
+                    .map(|&(i, j)| (i.into(), j.into()))
+                    .collect()
+            })
+            .collect();
+        Some(Self { len, layers })
+    }
+
+    /// Bitonic sort network, same as [`bitonic_sort_any`]
+    ///
+    /// 降序部分的比较器是反向的
+    ///
+    /// [`bitonic_sort_any`]: super::bitonic_sort_any
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::bitonic(8);
+    /// assert_eq!((net.size(), net.depth()), (24, 6));
+    /// ```
+    pub fn bitonic(len: usize) -> Self {
+        fn merge(net: &mut SortingNetwork, lo: usize, len: usize, rev: bool) {
+            if len < 2 { return }
+            let mid = 1 << (len-1).ilog2();
+            for i in lo..lo+len-mid {
+                let (a, b) = if rev { (i+mid, i) } else { (i, i+mid) };
+                net.push(a, b).unwrap();
+            }
+            merge(net, lo, mid, rev);
+            merge(net, lo+mid, len-mid, rev);
+        }
+        fn sort(net: &mut SortingNetwork, lo: usize, len: usize, rev: bool) {
+            if len < 2 { return }
+            let mid = len >> 1;
+            sort(net, lo, mid, !rev);
+            sort(net, lo+mid, len-mid, rev);
+            merge(net, lo, len, rev);
+        }
+        let mut net = Self::new(len);
+        sort(&mut net, 0, len, false);
+        net
+    }
+
+    /// Batcher odd-even merge sort network (merge exchange), support any length
+    ///
+    /// > Batcher 奇偶归并排序网络, 使用 Knuth 的归并交换算法(5.2.2 M)构造,
+    /// > 比较器数量少于双调排序网络, 同 [`odd_even_merge_sort`]
+    ///
+    /// [`odd_even_merge_sort`]: super::odd_even_merge_sort
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::odd_even_merge(8);
+    /// assert_eq!((net.size(), net.depth()), (19, 6));
+    /// ```
+    pub fn odd_even_merge(len: usize) -> Self {
+        let mut net = Self::new(len);
+        odd_even_merge_pairs(len, |i, j| net.push(i, j).unwrap());
+        net
+    }
+
+    /// Odd-even transposition sort network, same as [`odd_even_transposition_sort`]
+    ///
+    /// > 奇偶移项排序网络, 深度为`n`, 比较器数量为`n*(n-1)/2`
+    ///
+    /// [`odd_even_transposition_sort`]: super::odd_even_transposition_sort
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::odd_even_transposition(8);
+    /// assert_eq!((net.size(), net.depth()), (28, 8));
+    /// ```
+    pub fn odd_even_transposition(len: usize) -> Self {
+        let mut net = Self::new(len);
+        odd_even_transposition_pairs(len, |i, j| net.push(i, j).unwrap());
+        net
+    }
+
+    /// Pairwise sorting network (Parberry), support any length
+    ///
+    /// > 成对排序网络, 比较器数量与 Batcher 奇偶归并排序网络相同,
+    /// > 但是结构不同: 先两两比较, 再递归排序, 最后归并
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::pairwise(8);
+    /// assert_eq!((net.size(), net.depth()), (19, 6));
+    /// ```
+    pub fn pairwise(len: usize) -> Self {
+        let mut net = Self::new(len);
+        let mut a = 1;
+        while a < len {
+            let (mut b, mut c) = (a, 0);
+            while b < len {
+                net.push(b - a, b).unwrap();
+                b += 1;
+                c = (c + 1) % a;
+                if c == 0 { b += a }
+            }
+            a <<= 1;
+        }
+        a >>= 2;
+        let mut e = 1;
+        while a > 0 {
+            let mut d = e;
+            while d > 0 {
+                let (mut b, mut c) = ((d + 1) * a, 0);
+                while b < len {
+                    net.push(b - d * a, b).unwrap();
+                    b += 1;
+                    c = (c + 1) % a;
+                    if c == 0 { b += a }
+                }
+                d >>= 1;
+            }
+            a >>= 1;
+            e = (e << 1) + 1;
+        }
+        net
+    }
+
+    /// Count of wires
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn layers(&self) -> &[Vec<(usize, usize)>] {
+        &self.layers
+    }
+
+    /// All comparators in order
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.layers.iter().flatten().copied()
+    }
+
+    /// Count of comparators
+    pub fn size(&self) -> usize {
+        self.layers.iter().map(Vec::len).sum()
+    }
+
+    /// Count of layers
+    pub fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Apply network on `arr`
+    ///
+    /// # Panics
+    /// `arr.len() != self.len()`
+    pub fn apply<T, F>(&self, arr: &mut [T], mut lt: F)
+    where F: FnMut(&T, &T) -> bool,
+    {
+        assert_eq!(arr.len(), self.len, "network length mismatch");
+        for (i, j) in self.pairs() {
+            if cmp!(lt(arr[i],> arr[j])) {
+                arr.swap(i, j)
+            }
+        }
+    }
+
+    /// Check network is a sorting network by 0-1 principle
+    ///
+    /// > 0-1 原则: 比较网络能排序所有 0-1 序列, 那么它就能排序任意序列.
+    /// > 所以穷举所有`2^n`个 0-1 输入即可验证, 每次使用位运算并行检查 64 个输入
+    ///
+    /// return a failed input if it isn't a sorting network
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// assert!(SortingNetwork::odd_even_merge(10).verify().is_ok());
+    ///
+    /// let net = SortingNetwork::from_pairs(3, [(0, 1), (1, 2)]).unwrap();
+    /// assert_eq!(net.verify(), Err(vec![true, true, false]));
+    /// ```
+    pub fn verify(&self) -> Result<(), Vec<bool>> {
+        assert!(self.len < 64, "too many wires to verify");
+        const LANES: [u64; 6] = [
+            0xAAAA_AAAA_AAAA_AAAA,
+            0xCCCC_CCCC_CCCC_CCCC,
+            0xF0F0_F0F0_F0F0_F0F0,
+            0xFF00_FF00_FF00_FF00,
+            0xFFFF_0000_FFFF_0000,
+            0xFFFF_FFFF_0000_0000,
+        ];
+        let total = 1u64 << self.len;
+        let valid = if total < 64 { (1 << total) - 1 } else { u64::MAX };
+        let mut wires = vec![0u64; self.len];
+        for base in (0..total).step_by(64) {
+            // wires[w] 的第 t 位是输入`base + t`的第 w 位
+            for (w, wire) in wires.iter_mut().enumerate() {
+                *wire = match LANES.get(w) {
+                    Some(&lane) => lane,
+                    None if base >> w & 1 == 1 => u64::MAX,
+                    None => 0,
+                };
+            }
+            for (i, j) in self.pairs() {
+                let (a, b) = (wires[i], wires[j]);
+                (wires[i], wires[j]) = (a & b, a | b);
+            }
+            let bad = wires.windows(2)
+                .fold(0, |bad, w| bad | (w[0] & !w[1])) & valid;
+            if bad != 0 {
+                let input = base + bad.trailing_zeros() as u64;
+                return Err((0..self.len).map(|w| input >> w & 1 == 1).collect());
+            }
+        }
+        Ok(())
+    }
+
+    /// Reverse comparators `(i, j)` where `i > j` into standard form
+    ///
+    /// > 将反向比较器转换为标准形式, 也就是所有比较器都是`i < j`,
+    /// > 交换之后所有的线的编号, 以保持网络的功能不变 (Knuth 5.3.4 习题 16)
+    ///
+    /// # Example
+    /// ```
+    /// # use sorts_rs::other::SortingNetwork;
+    /// let net = SortingNetwork::bitonic(6).standardize();
+    /// assert!(net.pairs().all(|(i, j)| i < j));
+    /// assert!(net.verify().is_ok());
+    /// ```
+    pub fn standardize(&self) -> Self {
+        let mut pairs = self.pairs().collect::<Vec<_>>();
+        for k in 0..pairs.len() {
+            let (i, j) = pairs[k];
+            if i < j { continue }
+            let swap = |x: usize| if x == i { j } else if x == j { i } else { x };
+            for pair in &mut pairs[k..] {
+                *pair = (swap(pair.0), swap(pair.1));
+            }
+        }
+        Self::from_pairs(self.len, pairs).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::other::bitonic_sort_any;
+
+    #[test]
+    fn optimal_test() {
+        let sizes = [0, 0, 1, 3, 5, 9, 12, 16, 19, 25, 29, 35, 39, 45, 51, 56, 60];
+        for (len, size) in sizes.into_iter().enumerate() {
+            let net = SortingNetwork::optimal(len).unwrap();
+            assert_eq!(net.size(), size, "len {len}");
+            assert!(net.verify().is_ok(), "len {len}");
+            let layers = net.layers().to_vec();
+            assert_eq!(SortingNetwork::from_layers(len, layers), Ok(net));
+        }
+    }
+
+    #[test]
+    fn constructors_test() {
+        for len in 0..=18 {
+            let nets = [
+                SortingNetwork::bitonic(len),
+                SortingNetwork::odd_even_merge(len),
+                SortingNetwork::pairwise(len),
+                SortingNetwork::odd_even_transposition(len),
+                SortingNetwork::bitonic(len).standardize(),
+            ];
+            for net in nets {
+                assert_eq!(net.len(), len);
+                assert!(net.verify().is_ok(), "len {len} {net:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn apply_test() {
+        for len in 0..100 {
+            let data = (0..len).map(|i| (i * 7919 + len) % 13).collect::<Vec<_>>();
+            let mut expected = data.clone();
+            bitonic_sort_any(&mut expected, usize::lt);
+
+            let mut x = data.clone();
+            SortingNetwork::bitonic(len).apply(&mut x, usize::lt);
+            assert_eq!(x, expected);
+
+            let mut x = data.clone();
+            SortingNetwork::odd_even_merge(len).apply(&mut x, usize::lt);
+            assert_eq!(x, expected);
+        }
+    }
+
+    #[test]
+    fn verify_test() {
+        let mut net = SortingNetwork::optimal(8).unwrap();
+        net.layers.last_mut().unwrap().pop();
+        let input = net.verify().unwrap_err();
+        let mut output = input.clone();
+        net.apply(&mut output, bool::lt);
+        assert!(!output.is_sorted());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::cmp;
+
+/// Comparators of Batcher odd-even merge sort (merge exchange), in order
+///
+/// > 使用 Knuth 的归并交换算法(5.2.2 M), 支持任意长度
+pub(super) fn odd_even_merge_pairs(len: usize, mut f: impl FnMut(usize, usize)) {
+    if len < 2 { return }
+    let t = (len-1).ilog2() + 1;
+    let mut p = 1 << (t-1);
+    while p > 0 {
+        let (mut q, mut r, mut d) = (1 << (t-1), 0, p);
+        loop {
+            for i in (0..len-d).filter(|&i| i & p == r) {
+                f(i, i+d)
+            }
+            if q == p { break }
+            (d, q, r) = (q - p, q >> 1, p);
+        }
+        p >>= 1;
+    }
+}
+
+/// Comparators of odd-even transposition sort, in order
+///
+/// > 共`n`轮, 交替比较`(2i, 2i+1)`与`(2i+1, 2i+2)`
+pub(super) fn odd_even_transposition_pairs(len: usize, mut f: impl FnMut(usize, usize)) {
+    let rounds = if len < 2 { 0 } else { len };
+    for round in 0..rounds {
+        for i in (round & 1..len-1).step_by(2) {
+            f(i, i+1)
+        }
+    }
+}
+
+/// Batcher odd-even merge sort
+///
+/// > Batcher 奇偶归并排序, 类似双调排序, 也是一种排序网络,
+/// > 比较顺序与输入数据无关, 但是比较器数量比双调排序更少,
+/// > 复杂度为`O(n*log(n)^2)`
+/// >
+/// > 比较顺序与 [`SortingNetwork::odd_even_merge`] 相同
+///
+/// 支持任意长度
+///
+/// [`SortingNetwork::odd_even_merge`]: super::SortingNetwork::odd_even_merge
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::odd_even_merge_sort;
+/// let lt = i32::lt;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4, 8];
+/// odd_even_merge_sort(&mut arr, lt);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 8, 9]);
+/// ```
+pub fn odd_even_merge_sort<T, F>(arr: &mut [T], mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    odd_even_merge_pairs(arr.len(), |i, j| {
+        if cmp!(lt(arr[i],> arr[j])) {
+            arr.swap(i, j)
+        }
+    })
+}
+
+/// Odd-even transposition sort (brick sort)
+///
+/// > 奇偶移项排序, 也称砖排序, 是冒泡排序的并行形式,
+/// > 每轮中的比较互不相关, 固定进行`n`轮即可完成排序,
+/// > 复杂度为`O(n^2)`, 但是并行时只需`O(n)`轮
+/// >
+/// > 比较顺序与 [`SortingNetwork::odd_even_transposition`] 相同
+///
+/// 支持任意长度
+///
+/// [`SortingNetwork::odd_even_transposition`]: super::SortingNetwork::odd_even_transposition
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::odd_even_transposition_sort;
+/// let lt = i32::lt;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4, 8];
+/// odd_even_transposition_sort(&mut arr, lt);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 8, 9]);
+/// ```
+pub fn odd_even_transposition_sort<T, F>(arr: &mut [T], mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    odd_even_transposition_pairs(arr.len(), |i, j| {
+        if cmp!(lt(arr[i],> arr[j])) {
+            arr.swap(i, j)
+        }
+    })
+}
+
+#[test]
+fn odd_even_sorts_test() {
+    use super::{bitonic_sort_any, SortingNetwork};
+
+    for len in 0..=100 {
+        let data = (0..len).map(|i| (i * 7919 + len) % 31).collect::<Vec<_>>();
+        let mut expected = data.clone();
+        expected.sort();
+
+        let mut x = data.clone();
+        odd_even_merge_sort(&mut x, usize::lt);
+        assert_eq!(x, expected, "len {len}");
+
+        let mut x = data.clone();
+        odd_even_transposition_sort(&mut x, usize::lt);
+        assert_eq!(x, expected, "len {len}");
+
+        // 比较次数与网络大小一致, 且奇偶归并比双调排序更少
+        let count = |sort: crate::registry::LtSortFn<usize>| {
+            let mut count = 0;
+            sort(&mut data.clone(), &mut |a, b| { count += 1; a < b });
+            count
+        };
+        let merge = count(|arr, lt| odd_even_merge_sort(arr, lt));
+        let bitonic = count(|arr, lt| bitonic_sort_any(arr, lt));
+        let transposition = count(|arr, lt| odd_even_transposition_sort(arr, lt));
+        assert_eq!(merge, SortingNetwork::odd_even_merge(len).size());
+        assert_eq!(bitonic, SortingNetwork::bitonic(len).size());
+        assert_eq!(transposition, SortingNetwork::odd_even_transposition(len).size());
+        assert!(merge <= bitonic, "len {len}");
+    }
+}
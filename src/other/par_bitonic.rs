@@ -0,0 +1,143 @@
+use std::{iter::zip, num::NonZero, thread};
+
+use super::bitonic::{
+    bitonic_any_mid,
+    bitonic_merge_any_inner,
+    bitonic_sort_any_inner,
+    compare_swap,
+};
+
+/// Default sequential cutoff of [`par_bitonic_sort`]
+pub const PAR_BITONIC_CUTOFF: usize = 1 << 12;
+
+/// 将`threads`个线程分给两个子任务
+fn split_threads(threads: usize) -> (usize, usize) {
+    (threads >> 1, threads - (threads >> 1))
+}
+
+fn par_bitonic_merge_inner<T, F>(
+    arr: &mut [T],
+    lt: &F,
+    rev: bool,
+    cutoff: usize,
+    threads: usize,
+)
+where T: Send,
+      F: Fn(&T, &T) -> bool + Sync,
+{
+    if arr.len() <= cutoff || threads < 2 {
+        return bitonic_merge_any_inner(arr, &mut { lt }, rev);
+    }
+    let mid = bitonic_any_mid(arr.len());
+    let (lo, hi) = arr.split_at_mut(mid);
+
+    // 每一对比较交换互不相关, 分块并行
+    let chunk = hi.len().div_ceil(threads).max(cutoff);
+    thread::scope(|scope| {
+        for (lo, hi) in zip(lo.chunks_mut(chunk), hi.chunks_mut(chunk)) {
+            scope.spawn(move || {
+                for (a, b) in zip(lo, hi) {
+                    compare_swap(a, b, &mut { lt }, rev)
+                }
+            });
+        }
+    });
+
+    let (lo_threads, hi_threads) = split_threads(threads);
+    thread::scope(|scope| {
+        scope.spawn(|| par_bitonic_merge_inner(lo, lt, rev, cutoff, lo_threads));
+        par_bitonic_merge_inner(hi, lt, rev, cutoff, hi_threads);
+    });
+}
+
+fn par_bitonic_sort_inner<T, F>(
+    arr: &mut [T],
+    lt: &F,
+    rev: bool,
+    cutoff: usize,
+    threads: usize,
+)
+where T: Send,
+      F: Fn(&T, &T) -> bool + Sync,
+{
+    if arr.len() <= cutoff || threads < 2 {
+        return bitonic_sort_any_inner(arr, &mut { lt }, rev);
+    }
+    let mid = arr.len() >> 1;
+    let (lo, hi) = arr.split_at_mut(mid);
+
+    let (lo_threads, hi_threads) = split_threads(threads);
+    thread::scope(|scope| {
+        scope.spawn(|| par_bitonic_sort_inner(lo, lt, !rev, cutoff, lo_threads));
+        par_bitonic_sort_inner(hi, lt, rev, cutoff, hi_threads);
+    });
+
+    par_bitonic_merge_inner(arr, lt, rev, cutoff, threads);
+}
+
+/// Parallel Bitonic Sort, like [`par_bitonic_sort`], but use custom cutoff
+///
+/// 长度不超过`cutoff`的子网络将在当前线程中顺序执行
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::par_bitonic_sort_with_cutoff;
+/// let lt = i32::lt;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4, 8];
+/// par_bitonic_sort_with_cutoff(&mut arr, 2, lt);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 8, 9]);
+/// ```
+pub fn par_bitonic_sort_with_cutoff<T, F>(arr: &mut [T], cutoff: usize, lt: F)
+where T: Send,
+      F: Fn(&T, &T) -> bool + Sync,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZero::get);
+    par_bitonic_sort_inner(arr, &lt, false, cutoff.max(1), threads)
+}
+
+/// Parallel Bitonic Sort
+///
+/// > 多线程双调排序, 比较网络与 [`bitonic_sort_any`] 完全相同,
+/// > 网络中互不相关的比较交换与子网络被分配到多个线程中执行,
+/// > 所以结果也与 [`bitonic_sort_any`] 相同
+///
+/// 支持任意长度, 使用 [`PAR_BITONIC_CUTOFF`] 作为顺序执行的阈值
+///
+/// [`bitonic_sort_any`]: super::bitonic_sort_any
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::par_bitonic_sort;
+/// let lt = i32::lt;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// par_bitonic_sort(&mut arr, lt);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9]);
+/// ```
+pub fn par_bitonic_sort<T, F>(arr: &mut [T], lt: F)
+where T: Send,
+      F: Fn(&T, &T) -> bool + Sync,
+{
+    par_bitonic_sort_with_cutoff(arr, PAR_BITONIC_CUTOFF, lt)
+}
+
+#[test]
+fn par_bitonic_sort_test() {
+    use super::bitonic_sort_any;
+
+    let gen = |len: usize| (0..len).map(|i| (i * 7919 + len) % 997).collect::<Vec<_>>();
+    for len in 0..=1000 {
+        let mut x = gen(len);
+        let mut y = x.clone();
+        par_bitonic_sort_with_cutoff(&mut x, 8, usize::lt);
+        bitonic_sort_any(&mut y, usize::lt);
+        assert_eq!(x, y, "len {len}");
+    }
+    for len in [1 << 16, 100_000] {
+        let mut x = gen(len);
+        let mut y = x.clone();
+        par_bitonic_sort(&mut x, usize::lt);
+        bitonic_sort_any(&mut y, usize::lt);
+        assert_eq!(x, y, "len {len}");
+    }
+}
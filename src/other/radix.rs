@@ -1,30 +1,45 @@
-use std::{fmt::Debug, mem};
+use std::{iter::zip, mem};
 
-fn bit_high(n: usize) -> u32 {
-    match n {
-        0 => 0,
-        n => n.ilog2(),
-    }
+use crate::basic::insert_sort;
+
+use super::{count::permute, RadixKey, RadixUint};
+
+/// 最大键的有效位数
+fn key_bits<K: RadixUint>(keys: impl Iterator<Item = K>) -> u32 {
+    keys.max().map_or(0, |max| u128::BITS - max.to_u128().leading_zeros())
 }
 
 /// Radix Sort (LSD) binary
 ///
+/// 支持所有实现了 [`RadixKey`] 的类型, 如有符号整数与浮点数
+///
 /// # Example
 /// ```
 /// # use sorts_rs::other::radix_sort;
 /// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
 /// radix_sort(&mut arr);
 /// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9]);
+///
+/// let mut arr = [3, -1, 5, i32::MIN, 0];
+/// radix_sort(&mut arr);
+/// assert_eq!(arr, [i32::MIN, -1, 0, 3, 5]);
+///
+/// let mut arr = [2.5, -0.0, -3.0, f64::INFINITY, 0.0];
+/// radix_sort(&mut arr);
+/// assert_eq!(arr.map(f64::to_bits), [-3.0, -0.0, 0.0, 2.5, f64::INFINITY].map(f64::to_bits));
 /// ```
 pub fn radix_sort<T>(arr: &mut [T])
-where T: TryInto<usize> + Default + Copy,
-      T::Error: Debug,
+where T: RadixKey,
 {
     radix_sort_with_buf(arr, &mut Vec::with_capacity(arr.len()>>1))
 }
 
 /// Radix Sort (LSD) binary
 ///
+/// 每趟只分配一位, 较大的值需要更多趟数, 见 [`radix_sort_radix`]
+///
+/// 即使高位已经能区分所有元素, 依然会访问所有位, 见 [`msd_radix_sort`]
+///
 /// # Example
 /// ```
 /// # use sorts_rs::other::radix_sort_with_buf;
@@ -33,33 +48,384 @@ where T: TryInto<usize> + Default + Copy,
 /// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9]);
 /// ```
 pub fn radix_sort_with_buf<T>(arr: &mut [T], buf: &mut Vec<T>)
-where T: TryInto<usize> + Default + Copy,
-      T::Error: Debug,
+where T: RadixKey,
 {
     if arr.len() < 2 { return }
     buf.clear();
-    buf.reserve(arr.len() - arr.len() >> 2);
-
-    let radix = arr.iter()
-        .map(|&n| bit_high(n.try_into().unwrap()))
-        .max()
-        .unwrap_or_default();
-
-    for d in 0..radix+1 {
-        let mut j = 0;
-        for i in 0..arr.len() {
-            let cur = &mut arr[i];
-            if (*cur).try_into().unwrap() >> d & 1 == 1 {
-                buf.push(mem::take(cur))
+    // 每趟 buf 中是该位为 1 的元素, 预先留出 3/4 的空间, 避免第一趟中反复扩容
+    buf.reserve(arr.len() - (arr.len() >> 2));
+
+    for d in 0..key_bits(arr.iter().map(|ele| ele.to_radix_key())) {
+        let mut st = PassState { arr: &mut *arr, buf: &mut *buf, j: 0 };
+        for i in 0..st.arr.len() {
+            let cur = st.arr[i];
+            if cur.to_radix_key().digit(d, 1) == 1 {
+                st.buf.push(cur)
             } else {
-                arr[j] = mem::take(cur);
-                j += 1;
+                st.arr[st.j] = cur;
+                st.j += 1;
             }
         }
 
-        debug_assert_eq!(buf.len(), arr.len() - j);
+        debug_assert_eq!(st.buf.len(), st.arr.len() - st.j);
+    }
+}
 
-        buf.drain(..).zip(&mut arr[j..])
+/// 一趟分配的状态, `buf`中的元素将写入`arr[j..]`中的空位
+///
+/// drop 时写回, 这样即使 panic 也不会丢失元素
+struct PassState<'a, T> {
+    arr: &'a mut [T],
+    buf: &'a mut Vec<T>,
+    j: usize,
+}
+impl<T> Drop for PassState<'_, T> {
+    fn drop(&mut self) {
+        self.buf.drain(..).zip(&mut self.arr[self.j..])
             .for_each(|(ele, cur)| *cur = ele);
     }
 }
+
+/// Radix Sort (LSD), use `2^BITS` as radix
+///
+/// > 基数排序, 每趟按`BITS`位(常用 4, 8, 11)分配,
+/// > 相比 [`radix_sort`] 的每趟一位, 趟数减少为约`1/BITS`
+/// >
+/// > 先一次遍历统计所有趟的计数, 再通过前缀和计算每个桶的起始位置进行分配,
+/// > 所有元素在该位都相同的趟将会被跳过, 两个缓冲区交替作为分配的来源与目标
+///
+/// 结果与 [`radix_sort`] 相同, 需要`O(n + 2^BITS * passes)`的辅助空间,
+/// `BITS`需要在`1..=16`中
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::radix_sort_radix;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4, 0x1ff];
+/// radix_sort_radix::<8, _>(&mut arr);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9, 0x1ff]);
+///
+/// let mut arr = [u64::MAX, 3, u64::MAX - 1, 0];
+/// radix_sort_radix::<11, _>(&mut arr);
+/// assert_eq!(arr, [0, 3, u64::MAX - 1, u64::MAX]);
+/// ```
+/// too many bits
+/// ```compile_fail
+/// # use sorts_rs::other::radix_sort_radix;
+/// radix_sort_radix::<17, u32>(&mut [3, 1, 2]);
+/// ```
+pub fn radix_sort_radix<const BITS: u32, T>(arr: &mut [T])
+where T: RadixKey,
+{
+    if arr.len() < 2 { return }
+
+    // 先全部转换, 这样转换失败 panic 时`arr`还未被修改
+    let src = arr.iter()
+        .map(|&ele| (ele.to_radix_key(), ele))
+        .collect::<Vec<_>>();
+
+    for (cur, (_, ele)) in zip(arr, radix_sort_pairs::<BITS, _, _>(src)) {
+        *cur = ele
+    }
+}
+
+/// 按键对`(key, value)`进行 LSD 基数排序, 是稳定的
+fn radix_sort_pairs<const BITS: u32, K, V>(mut src: Vec<(K, V)>) -> Vec<(K, V)>
+where K: RadixUint,
+      V: Copy,
+{
+    const { assert!(BITS > 0 && BITS <= 16, "expect BITS in 1..=16") }
+    let (size, mask) = (1 << BITS, (1 << BITS) - 1);
+    let passes = key_bits(src.iter().map(|&(key, _)| key)).div_ceil(BITS);
+
+    let mut counts = vec![0usize; (passes as usize) << BITS];
+    for &(key, _) in &src {
+        for (pass, count) in zip(0.., counts.chunks_exact_mut(size)) {
+            count[key.digit(pass * BITS, mask)] += 1;
+        }
+    }
+
+    let mut dst = src.clone();
+    for (pass, count) in zip(0.., counts.chunks_exact_mut(size)) {
+        // 所有元素在该位都相同, 分配后顺序不变
+        if count.contains(&src.len()) { continue }
+        let mut sum = 0;
+        for cur in &mut *count {
+            (*cur, sum) = (sum, sum + *cur);
+        }
+        for &(key, ele) in &src {
+            let pos = &mut count[key.digit(pass * BITS, mask)];
+            dst[*pos] = (key, ele);
+            *pos += 1;
+        }
+        mem::swap(&mut src, &mut dst);
+    }
+    src
+}
+
+/// Radix Sort (LSD) by key, stable
+///
+/// > 按键基数排序, 先提取所有元素的键 (见 [`RadixKey`]),
+/// > 对键与下标进行 8 位一趟的基数排序, 再将整个元素移动到排序后的位置,
+/// > 键相同的元素保持原有顺序
+/// >
+/// > 每个元素的键只提取一次, 元素只通过交换移动, 不需要`Copy`或`Default`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::radix_sort_by_key;
+/// #[derive(Debug, PartialEq)]
+/// struct Event { user: u32, time: i64, name: String }
+/// let event = |user, time, name: &str| Event { user, time, name: name.into() };
+///
+/// let mut events = vec![
+///     event(7, 30, "c"),
+///     event(3, -10, "a"),
+///     event(7, -20, "b"),
+///     event(3, 20, "d"),
+/// ];
+/// radix_sort_by_key(&mut events, |e| e.user);
+/// let names = events.iter().map(|e| &*e.name).collect::<Vec<_>>();
+/// assert_eq!(names, ["a", "d", "c", "b"]);
+///
+/// radix_sort_by_key(&mut events, |e| (e.user, e.time));
+/// let names = events.iter().map(|e| &*e.name).collect::<Vec<_>>();
+/// assert_eq!(names, ["a", "d", "b", "c"]);
+/// ```
+pub fn radix_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where K: RadixKey,
+      F: FnMut(&T) -> K,
+{
+    if arr.len() < 2 { return }
+    let src = arr.iter()
+        .enumerate()
+        .map(|(i, ele)| (key(ele).to_radix_key(), i))
+        .collect::<Vec<_>>();
+
+    let mut dest = vec![0; arr.len()];
+    for (pos, (_, i)) in radix_sort_pairs::<8, _, _>(src).into_iter().enumerate() {
+        dest[i] = pos
+    }
+    permute(arr, &mut dest)
+}
+
+/// MSD 基数排序中, 长度不超过此值的桶使用插入排序
+pub const MSD_INSERT_CUTOFF: usize = 32;
+
+/// 每个字节一个桶, 另外桶`0`用于已经结束的字节串
+const MSD_BUCKETS: usize = 257;
+
+/// 整数键从高位开始的第`level`个字节, 作为桶的下标
+fn key_digit<T: RadixKey>(ele: &T, level: usize) -> usize {
+    let bytes = T::KEY_BITS.div_ceil(8) as usize;
+    match bytes.checked_sub(level + 1) {
+        Some(i) => ele.to_radix_key().digit(i as u32 * 8, 0xff) + 1,
+        None => 0,
+    }
+}
+
+/// 字节串的第`level`个字节, 作为桶的下标, 结束时为`0`
+fn bytes_digit<T: AsRef<[u8]>>(ele: &T, level: usize) -> usize {
+    ele.as_ref().get(level).map_or(0, |&b| usize::from(b) + 1)
+}
+
+/// 统计各个桶的元素数量
+fn bucket_counts<T, D>(arr: &[T], level: usize, digit: &D) -> [usize; MSD_BUCKETS]
+where D: Fn(&T, usize) -> usize,
+{
+    let mut counts = [0; MSD_BUCKETS];
+    for ele in arr {
+        counts[digit(ele, level)] += 1;
+    }
+    counts
+}
+
+/// 各个桶的起始位置
+fn bucket_starts(counts: &[usize; MSD_BUCKETS]) -> [usize; MSD_BUCKETS] {
+    let mut starts = [0; MSD_BUCKETS];
+    let mut sum = 0;
+    for (start, &count) in zip(&mut starts, counts) {
+        (*start, sum) = (sum, sum + count);
+    }
+    starts
+}
+
+fn msd_radix_inner<T, D, F>(
+    arr: &mut [T],
+    buf: &mut Vec<T>,
+    mut level: usize,
+    digit: &D,
+    lt: &mut F,
+)
+where T: Default,
+      D: Fn(&T, usize) -> usize,
+      F: FnMut(&T, &T) -> bool,
+{
+    if arr.len() <= MSD_INSERT_CUTOFF {
+        return insert_sort(arr, lt);
+    }
+
+    // 所有元素都在同一个桶中时直接处理下一个字节, 避免过深的递归
+    let (digits, counts) = loop {
+        // 先计算所有桶下标, 这样 panic 时`arr`还未被修改
+        let digits = arr.iter()
+            .map(|ele| digit(ele, level))
+            .collect::<Vec<_>>();
+        let mut counts = [0; MSD_BUCKETS];
+        for &d in &digits {
+            counts[d] += 1;
+        }
+        if counts[0] == arr.len() { return }
+        if !counts.contains(&arr.len()) { break (digits, counts) }
+        level += 1;
+    };
+
+    let starts = bucket_starts(&counts);
+    let mut pos = starts;
+    buf.clear();
+    buf.resize_with(arr.len(), T::default);
+    for (ele, d) in zip(&mut *arr, digits) {
+        mem::swap(ele, &mut buf[pos[d]]);
+        pos[d] += 1;
+    }
+    arr.swap_with_slice(buf);
+
+    // 桶`0`中的元素已经结束, 都是相等的
+    for (&start, &count) in zip(&starts, &counts).skip(1) {
+        if count > 1 {
+            msd_radix_inner(&mut arr[start..start+count], buf, level + 1, digit, lt)
+        }
+    }
+}
+
+fn american_flag_inner<T, D, F>(
+    arr: &mut [T],
+    mut level: usize,
+    digit: &D,
+    lt: &mut F,
+)
+where D: Fn(&T, usize) -> usize,
+      F: FnMut(&T, &T) -> bool,
+{
+    if arr.len() <= MSD_INSERT_CUTOFF {
+        return insert_sort(arr, lt);
+    }
+
+    // 所有元素都在同一个桶中时直接处理下一个字节, 避免过深的递归
+    let counts = loop {
+        let counts = bucket_counts(arr, level, digit);
+        if counts[0] == arr.len() { return }
+        if !counts.contains(&arr.len()) { break counts }
+        level += 1;
+    };
+
+    // 每个桶从头开始, 将不属于该桶的元素沿着置换环交换到其所属桶的头部
+    let starts = bucket_starts(&counts);
+    let mut heads = starts;
+    for b in 0..MSD_BUCKETS {
+        let end = starts[b] + counts[b];
+        while heads[b] < end {
+            let d = digit(&arr[heads[b]], level);
+            if d != b {
+                arr.swap(heads[b], heads[d]);
+            }
+            heads[d] += 1;
+        }
+    }
+
+    // 桶`0`中的元素已经结束, 都是相等的
+    for (&start, &count) in zip(&starts, &counts).skip(1) {
+        if count > 1 {
+            american_flag_inner(&mut arr[start..start+count], level + 1, digit, lt)
+        }
+    }
+}
+
+/// Radix Sort (MSD)
+///
+/// > 高位优先基数排序, 从键的最高字节开始按字节分配到 256 个桶中,
+/// > 再递归排序每个桶, 高位已经能区分的元素不再访问其低位,
+/// > 较小的桶 (见 [`MSD_INSERT_CUTOFF`]) 使用插入排序
+/// >
+/// > 所有元素该字节都相同时直接处理下一个字节
+///
+/// 需要`O(n)`的辅助空间, 字节串见 [`msd_radix_sort_bytes`],
+/// 原地的版本见 [`american_flag_sort`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::msd_radix_sort;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// msd_radix_sort(&mut arr);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9]);
+///
+/// let mut arr = (0..100).map(|i| (i * 37 % 100) as i64 - 50).collect::<Vec<_>>();
+/// msd_radix_sort(&mut arr);
+/// assert!(arr.is_sorted());
+/// ```
+pub fn msd_radix_sort<T>(arr: &mut [T])
+where T: RadixKey + Default,
+{
+    let mut lt = |a: &T, b: &T| a.to_radix_key() < b.to_radix_key();
+    msd_radix_inner(arr, &mut vec![], 0, &key_digit, &mut lt)
+}
+
+/// Radix Sort (MSD) for byte strings, in lexicographic order
+///
+/// > 字节串的高位优先基数排序, 类似 [`msd_radix_sort`],
+/// > 较短的字节串在其结束的位置被分到单独的桶中, 排在最前
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::msd_radix_sort_bytes;
+/// let mut arr = ["she", "sells", "sea", "shells", "by", "the", "", "sea"];
+/// msd_radix_sort_bytes(&mut arr);
+/// assert_eq!(arr, ["", "by", "sea", "sea", "sells", "she", "shells", "the"]);
+/// ```
+pub fn msd_radix_sort_bytes<T>(arr: &mut [T])
+where T: AsRef<[u8]> + Default,
+{
+    let mut lt = |a: &T, b: &T| a.as_ref() < b.as_ref();
+    msd_radix_inner(arr, &mut vec![], 0, &bytes_digit, &mut lt)
+}
+
+/// American Flag Sort, in-place MSD radix sort
+///
+/// > 美国国旗排序, 原地的高位优先基数排序, 先对当前字节计数得到每个桶的范围,
+/// > 再从每个桶的头部开始, 将元素沿着置换环交换到其所属的桶中,
+/// > 不需要额外的元素缓冲区, 但是不稳定
+///
+/// 字节串见 [`american_flag_sort_bytes`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::american_flag_sort;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// american_flag_sort(&mut arr);
+/// assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9]);
+///
+/// let mut arr = (0..100).map(|i| (i * 37 % 100) as f32 - 50.5).collect::<Vec<_>>();
+/// american_flag_sort(&mut arr);
+/// assert!(arr.is_sorted());
+/// ```
+pub fn american_flag_sort<T>(arr: &mut [T])
+where T: RadixKey,
+{
+    let mut lt = |a: &T, b: &T| a.to_radix_key() < b.to_radix_key();
+    american_flag_inner(arr, 0, &key_digit, &mut lt)
+}
+
+/// American Flag Sort for byte strings, in lexicographic order
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::american_flag_sort_bytes;
+/// let mut arr = vec![b"dab".to_vec(), b"add".to_vec(), b"cab".to_vec(), b"ad".to_vec()];
+/// american_flag_sort_bytes(&mut arr);
+/// assert_eq!(arr, [&b"ad"[..], b"add", b"cab", b"dab"]);
+/// ```
+pub fn american_flag_sort_bytes<T>(arr: &mut [T])
+where T: AsRef<[u8]>,
+{
+    let mut lt = |a: &T, b: &T| a.as_ref() < b.as_ref();
+    american_flag_inner(arr, 0, &bytes_digit, &mut lt)
+}
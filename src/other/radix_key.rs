@@ -0,0 +1,249 @@
+use std::fmt::Debug;
+
+/// Unsigned integer used as the key of [`RadixKey`]
+pub trait RadixUint: Copy + Ord + Debug {
+    /// 位数
+    const BITS: u32;
+
+    fn to_u128(self) -> u128;
+
+    /// 截断高位
+    fn from_u128(n: u128) -> Self;
+
+    /// `(self >> shift) & mask`, 移位超出位数时为`0`
+    fn digit(self, shift: u32, mask: usize) -> usize;
+}
+
+macro_rules! impl_radix_uint {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl RadixUint for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_u128(n: u128) -> Self {
+                    n as $ty
+                }
+
+                fn digit(self, shift: u32, mask: usize) -> usize {
+                    self.checked_shr(shift).unwrap_or(0) as usize & mask
+                }
+            }
+        )+
+    };
+}
+impl_radix_uint!(u8, u16, u32, u64, u128, usize);
+
+/// Map value to an order-preserving unsigned key
+///
+/// > 将值映射为保持顺序的无符号整数键, 用于基数排序与计数排序,
+/// > 即`a < b`当且仅当`a.to_radix_key() < b.to_radix_key()`,
+/// > 且映射是可逆的
+/// >
+/// > - 无符号整数: 其本身
+/// > - 有符号整数: 翻转符号位
+/// > - 浮点数: 正数翻转符号位, 负数翻转所有位,
+/// >   得到与 [`f64::total_cmp`] 相同的全序, 即`-NaN < -inf < -0.0 < 0.0 < inf < NaN`
+/// > - `char`, `bool`: 其编码
+/// > - 元组: 各元素的键依次拼接, 总位数不能超过 128
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::RadixKey;
+/// assert!((-1i32).to_radix_key() < 1i32.to_radix_key());
+/// assert!((-0.5f64).to_radix_key() < 0.0f64.to_radix_key());
+/// assert!((1u8, 'b').to_radix_key() > (0u8, 'c').to_radix_key());
+/// assert_eq!(i32::from_radix_key((-7i32).to_radix_key()), -7);
+/// ```
+/// tuple key too long
+/// ```compile_fail
+/// # use sorts_rs::other::RadixKey;
+/// (1u128, 2u8).to_radix_key();
+/// ```
+pub trait RadixKey: Copy {
+    type Key: RadixUint;
+
+    /// 键的有效位数
+    const KEY_BITS: u32 = <Self::Key as RadixUint>::BITS;
+
+    fn to_radix_key(self) -> Self::Key;
+
+    fn from_radix_key(key: Self::Key) -> Self;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl RadixKey for $ty {
+                type Key = $ty;
+
+                fn to_radix_key(self) -> Self::Key {
+                    self
+                }
+
+                fn from_radix_key(key: Self::Key) -> Self {
+                    key
+                }
+            }
+        )+
+    };
+}
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_radix_key_signed {
+    ($($ty:ty => $key:ty),+ $(,)?) => {
+        $(
+            impl RadixKey for $ty {
+                type Key = $key;
+
+                fn to_radix_key(self) -> Self::Key {
+                    self as $key ^ <$ty>::MIN as $key
+                }
+
+                fn from_radix_key(key: Self::Key) -> Self {
+                    (key ^ <$ty>::MIN as $key) as $ty
+                }
+            }
+        )+
+    };
+}
+impl_radix_key_signed! {
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+}
+
+macro_rules! impl_radix_key_float {
+    ($($ty:ty => $key:ty),+ $(,)?) => {
+        $(
+            impl RadixKey for $ty {
+                type Key = $key;
+
+                fn to_radix_key(self) -> Self::Key {
+                    const SIGN: $key = 1 << (<$key>::BITS - 1);
+                    let bits = self.to_bits();
+                    if bits & SIGN == 0 { bits ^ SIGN } else { !bits }
+                }
+
+                fn from_radix_key(key: Self::Key) -> Self {
+                    const SIGN: $key = 1 << (<$key>::BITS - 1);
+                    let bits = if key & SIGN == 0 { !key } else { key ^ SIGN };
+                    <$ty>::from_bits(bits)
+                }
+            }
+        )+
+    };
+}
+impl_radix_key_float! {
+    f32 => u32,
+    f64 => u64,
+}
+
+impl RadixKey for char {
+    type Key = u32;
+
+    fn to_radix_key(self) -> Self::Key {
+        self.into()
+    }
+
+    /// # Panics
+    /// 不是有效的`char`编码
+    fn from_radix_key(key: Self::Key) -> Self {
+        char::from_u32(key).expect("invalid char key")
+    }
+}
+
+impl RadixKey for bool {
+    type Key = u8;
+
+    fn to_radix_key(self) -> Self::Key {
+        self.into()
+    }
+
+    fn from_radix_key(key: Self::Key) -> Self {
+        key != 0
+    }
+}
+
+macro_rules! impl_radix_key_tuple {
+    ($first:ident $($rest:ident)+) => {
+        impl<$first: RadixKey, $($rest: RadixKey),+> RadixKey for ($first, $($rest),+) {
+            type Key = u128;
+
+            const KEY_BITS: u32 = $first::KEY_BITS $(+ $rest::KEY_BITS)+;
+
+            #[allow(non_snake_case)]
+            fn to_radix_key(self) -> Self::Key {
+                const { assert!(Self::KEY_BITS <= 128, "tuple key has more than 128 bits") }
+                let ($first, $($rest),+) = self;
+                let mut key = $first.to_radix_key().to_u128();
+                $(
+                    key = key << $rest::KEY_BITS | $rest.to_radix_key().to_u128();
+                )+
+                key
+            }
+
+            fn from_radix_key(key: Self::Key) -> Self {
+                let mut shift = Self::KEY_BITS;
+                let mut next = |bits| {
+                    shift -= bits;
+                    key >> shift
+                };
+                (
+                    $first::from_radix_key(RadixUint::from_u128(next($first::KEY_BITS))),
+                    $($rest::from_radix_key(RadixUint::from_u128(next($rest::KEY_BITS)))),+
+                )
+            }
+        }
+    };
+}
+impl_radix_key_tuple!(A B);
+impl_radix_key_tuple!(A B C);
+impl_radix_key_tuple!(A B C D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<T>(values: &[T], cmp: impl Fn(&T, &T) -> std::cmp::Ordering)
+    where T: RadixKey + Debug,
+    {
+        for a in values {
+            let key = a.to_radix_key();
+            let back = T::from_radix_key(key);
+            assert_eq!(back.to_radix_key(), key, "{a:?} -> {back:?}");
+            for b in values {
+                assert_eq!(key.cmp(&b.to_radix_key()), cmp(a, b), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn radix_key_test() {
+        check(&[i8::MIN, -100, -1, 0, 1, 100, i8::MAX], Ord::cmp);
+        check(&[i64::MIN, -1 << 40, -1, 0, 1, 1 << 40, i64::MAX], Ord::cmp);
+        check(&[i128::MIN, -1, 0, 1, i128::MAX], Ord::cmp);
+        check(&[0u16, 1, 255, 256, u16::MAX], Ord::cmp);
+        check(&[false, true], Ord::cmp);
+        check(&['\0', 'a', 'z', '\u{d7ff}', '\u{e000}', char::MAX], Ord::cmp);
+        check(&[
+            -f64::NAN, f64::NEG_INFINITY, f64::MIN, -1.5, -f64::MIN_POSITIVE,
+            -0.0, 0.0, f64::MIN_POSITIVE, 1.5, f64::MAX, f64::INFINITY, f64::NAN,
+        ], f64::total_cmp);
+        check(&[-f32::NAN, -1.0, -0.0, 0.0, 1e-40, 1.0, f32::NAN], f32::total_cmp);
+        check(&[
+            (-1i8, 'b'), (-1, 'c'), (0, '\0'), (0, char::MAX), (1, 'a'),
+        ], Ord::cmp);
+        check(&[
+            (false, 0u8, -1i16), (false, 0, 1), (false, 1, i16::MIN),
+            (true, 0, 0), (true, u8::MAX, i16::MAX),
+        ], Ord::cmp);
+        check(&[(0u64, 1u64), (1, 0), (u64::MAX, u64::MAX)], Ord::cmp);
+    }
+}
@@ -0,0 +1,540 @@
+//! 字符串排序, 利用字符串的公共前缀减少比较
+//!
+//! 比较排序每次比较都需要从头比较两个字符串, 当字符串有很长的公共前缀时,
+//! 大部分时间都花费在比较已知相等的前缀上. 这里的排序按字节处理字符串,
+//! 每个字节只需要被访问常数次
+//!
+//! 排序先对所有字符串的字节串引用进行排序, 最后再按排序结果移动元素本身,
+//! 所以支持任意实现了`AsRef<[u8]>`的类型, 如`&[u8]`, `&str`, `Vec<u8>`, `String`
+
+use std::{cmp::Ordering, iter::zip, mem};
+
+use crate::normal::NormalMergeState;
+
+use super::count::permute;
+
+/// 长度不超过此值的部分使用 LCP 插入排序
+pub const STRING_INSERT_CUTOFF: usize = 32;
+
+/// 突发排序中桶的最大容量, 超过时桶将被分裂为一个节点
+pub const BURST_LIMIT: usize = 1024;
+
+/// 每个字节一个桶, 另外桶`0`用于已经结束的字符串
+const BUCKETS: usize = 257;
+
+/// 字符串引用与其原本的下标
+type Str<'a> = (&'a [u8], usize);
+
+/// 第`depth`个字节, 作为桶的下标, 结束时为`0`
+fn digit(s: &[u8], depth: usize) -> usize {
+    s.get(depth).map_or(0, |&b| usize::from(b) + 1)
+}
+
+/// 从`depth`开始的公共前缀长度, 加上`depth`
+fn lcp_from(a: &[u8], b: &[u8], depth: usize) -> usize {
+    let (a, b) = (&a[depth.min(a.len())..], &b[depth.min(b.len())..]);
+    depth + zip(a, b).take_while(|(a, b)| a == b).count()
+}
+
+/// 对字符串引用进行排序, 再将元素移动到排序后的位置
+fn sort_by_refs<T, S>(arr: &mut [T], sort: S)
+where T: AsRef<[u8]>,
+      S: FnOnce(&mut [Str<'_>]),
+{
+    let mut dest = vec![0; arr.len()];
+    {
+        let mut refs = zip(arr.iter().map(AsRef::as_ref), 0..).collect::<Vec<_>>();
+        sort(&mut refs);
+        for (pos, &(_, i)) in refs.iter().enumerate() {
+            dest[i] = pos
+        }
+    }
+    permute(arr, &mut dest)
+}
+
+/// LCP insertion sort, all strings have common prefix of length `depth`
+///
+/// > 插入排序, 同时记录已排序部分相邻字符串的公共前缀长度 (LCP),
+/// > 插入时通过 LCP 推断与左侧字符串的大小关系, 只在无法推断时才比较字节,
+/// > 且比较从已知的公共前缀之后开始
+fn lcp_insert_sort(arr: &mut [Str<'_>], depth: usize) {
+    // lcp[k] = lcp(arr[k-1], arr[k])
+    let mut lcp = vec![depth; arr.len()];
+    for i in 1..arr.len() {
+        let x = arr[i].0;
+        let mut j = i;
+        // 始终有 h = lcp(arr[j-1], x)
+        let mut h = lcp_from(arr[j-1].0, x, depth);
+        let mut right = depth;
+        loop {
+            if x.get(h) >= arr[j-1].0.get(h) { break }
+            // x < arr[j-1]
+            right = h;
+            j -= 1;
+            if j == 0 { break }
+            let l = lcp[j];
+            match l.cmp(&h) {
+                // arr[j-1] < arr[j] 在位置 l 处区分, 而 x 在 l 处与 arr[j] 相同
+                Ordering::Less => { h = l; break },
+                // arr[j-1] 与 arr[j] 在 h 处相同, 所以 x < arr[j-1]
+                Ordering::Greater => (),
+                Ordering::Equal => h = lcp_from(arr[j-1].0, x, h),
+            }
+        }
+
+        arr[j..=i].rotate_right(1);
+        lcp[j..=i].rotate_right(1);
+        lcp[j] = h;
+        if j < i {
+            lcp[j+1] = right;
+        }
+    }
+}
+
+fn multikey_inner(mut arr: &mut [Str<'_>], mut depth: usize) {
+    loop {
+        if arr.len() <= STRING_INSERT_CUTOFF {
+            return lcp_insert_sort(arr, depth);
+        }
+
+        let mut samples = [0, arr.len() >> 1, arr.len() - 1].map(|i| digit(arr[i].0, depth));
+        samples.sort_unstable();
+        let pivot = samples[1];
+
+        // 三路划分: [..lt] < pivot, [lt..gt] == pivot, [gt..] > pivot
+        let (mut lt, mut i, mut gt) = (0, 0, arr.len());
+        while i < gt {
+            match digit(arr[i].0, depth).cmp(&pivot) {
+                Ordering::Less => {
+                    arr.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                },
+                Ordering::Greater => {
+                    gt -= 1;
+                    arr.swap(i, gt);
+                },
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        let (low, rest) = mem::take(&mut arr).split_at_mut(lt);
+        let (eq, high) = rest.split_at_mut(gt - lt);
+        multikey_inner(low, depth);
+        multikey_inner(high, depth);
+
+        // 等于部分均已结束, 都是相等的
+        if pivot == 0 { return }
+        arr = eq;
+        depth += 1;
+    }
+}
+
+/// Multikey Quicksort (three-way radix quicksort)
+///
+/// > 多键快速排序, Bentley 与 Sedgewick 提出,
+/// > 按当前字节选取枢轴, 将字符串三路划分为小于, 等于, 大于枢轴的三部分,
+/// > 小于与大于的部分继续按当前字节排序, 等于的部分按下一个字节排序
+/// >
+/// > 结合了快速排序与基数排序, 每次只比较一个字节, 已知相等的前缀不会被再次比较,
+/// > 不稳定
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::multikey_quicksort;
+/// let mut arr = ["she", "sells", "sea", "shells", "by", "the", "", "sea"];
+/// multikey_quicksort(&mut arr);
+/// assert_eq!(arr, ["", "by", "sea", "sea", "sells", "she", "shells", "the"]);
+/// ```
+pub fn multikey_quicksort<T: AsRef<[u8]>>(arr: &mut [T]) {
+    sort_by_refs(arr, |refs| multikey_inner(refs, 0))
+}
+
+fn msd_string_inner<'a>(arr: &mut [Str<'a>], buf: &mut [Str<'a>], mut depth: usize) {
+    if arr.len() <= STRING_INSERT_CUTOFF {
+        return lcp_insert_sort(arr, depth);
+    }
+
+    // 所有字符串都在同一个桶中时直接处理下一个字节, 避免过深的递归
+    let counts = loop {
+        let mut counts = [0; BUCKETS];
+        for &s in &*arr {
+            counts[digit(s.0, depth)] += 1;
+        }
+        if counts[0] == arr.len() { return }
+        if !counts.contains(&arr.len()) { break counts }
+        depth += 1;
+    };
+
+    let mut starts = [0; BUCKETS];
+    let mut sum = 0;
+    for (start, &count) in zip(&mut starts, &counts) {
+        (*start, sum) = (sum, sum + count);
+    }
+    let mut pos = starts;
+    for &s in &*arr {
+        let d = digit(s.0, depth);
+        buf[pos[d]] = s;
+        pos[d] += 1;
+    }
+    arr.copy_from_slice(buf);
+
+    // 桶`0`中的字符串已经结束, 都是相等的
+    for (&start, &count) in zip(&starts, &counts).skip(1) {
+        let range = start..start+count;
+        if count > 1 {
+            msd_string_inner(&mut arr[range.clone()], &mut buf[range], depth + 1)
+        }
+    }
+}
+
+/// MSD Radix Sort for strings, stable
+///
+/// > 字符串的高位优先基数排序, 按当前字节分配到桶中, 再按下一个字节递归排序每个桶,
+/// > 较小的桶 (见 [`STRING_INSERT_CUTOFF`]) 使用从公共前缀之后开始比较的
+/// > LCP 插入排序
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::msd_string_sort;
+/// let mut arr = vec![
+///     "/var/log/b.log".to_string(),
+///     "/var/lib/a".to_string(),
+///     "/usr/bin".to_string(),
+///     "/var/log/a.log".to_string(),
+/// ];
+/// msd_string_sort(&mut arr);
+/// assert_eq!(arr, ["/usr/bin", "/var/lib/a", "/var/log/a.log", "/var/log/b.log"]);
+/// ```
+pub fn msd_string_sort<T: AsRef<[u8]>>(arr: &mut [T]) {
+    sort_by_refs(arr, |refs| {
+        let mut buf = refs.to_vec();
+        msd_string_inner(refs, &mut buf, 0)
+    })
+}
+
+/// Burst trie 中的一个槽, 是未排序的字符串桶或者子节点
+enum Slot<'a> {
+    Bucket(Vec<Str<'a>>),
+    Node(Box<Node<'a>>),
+}
+
+/// Burst trie 的节点, 经过路径压缩
+///
+/// 节点中的所有字符串在`[..depth]`上都相同, 按第`depth`个字节划分到各个槽中
+struct Node<'a> {
+    /// 节点中的任意一个字符串
+    prefix: &'a [u8],
+    depth: usize,
+    children: Vec<Slot<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn new(prefix: &'a [u8], depth: usize) -> Self {
+        let children = (0..BUCKETS).map(|_| Slot::Bucket(vec![])).collect();
+        Self { prefix, depth, children }
+    }
+
+    /// 分裂在`[..from]`上相同的字符串, 节点在它们之后的公共前缀处划分
+    fn burst(strs: Vec<Str<'a>>, from: usize) -> Self {
+        let prefix = strs[0].0;
+        let depth = strs.iter()
+            .map(|s| lcp_from(prefix, s.0, from))
+            .min()
+            .unwrap_or(from);
+
+        let mut buckets = (0..BUCKETS).map(|_| vec![]).collect::<Vec<_>>();
+        for s in strs {
+            buckets[digit(s.0, depth)].push(s);
+        }
+        let children = buckets.into_iter()
+            .enumerate()
+            .map(|(d, bucket)| {
+                if d != 0 && bucket.len() > BURST_LIMIT {
+                    Slot::Node(Box::new(Self::burst(bucket, depth + 1)))
+                } else {
+                    Slot::Bucket(bucket)
+                }
+            })
+            .collect();
+        Self { prefix, depth, children }
+    }
+
+    /// 插入字符串, `s`需要在`[..depth]`上与节点中的字符串相同
+    fn insert(&mut self, s: Str<'a>) {
+        let d = digit(s.0, self.depth);
+        let from = self.depth + 1;
+        match &mut self.children[d] {
+            Slot::Bucket(bucket) => {
+                bucket.push(s);
+                // 桶`0`中的字符串已经结束, 都是相等的, 无需分裂
+                if d != 0 && bucket.len() > BURST_LIMIT {
+                    let strs = mem::take(bucket);
+                    self.children[d] = Slot::Node(Box::new(Self::burst(strs, from)));
+                }
+            },
+            Slot::Node(child) => {
+                let split = lcp_from(s.0, child.prefix, from);
+                if split >= child.depth {
+                    return child.insert(s);
+                }
+                // 在被压缩的路径中分叉, 插入一个新的节点
+                let mut node = Self::new(child.prefix, split);
+                let child_digit = digit(child.prefix, split);
+                node.children[digit(s.0, split)] = Slot::Bucket(vec![s]);
+                node.children[child_digit] = mem::replace(
+                    &mut self.children[d],
+                    Slot::Bucket(vec![]),
+                );
+                self.children[d] = Slot::Node(Box::new(node));
+            },
+        }
+    }
+
+    /// 按顺序遍历节点, 排序每个桶并输出
+    fn collect(self, out: &mut Vec<Str<'a>>) {
+        for (d, slot) in self.children.into_iter().enumerate() {
+            match slot {
+                Slot::Bucket(mut bucket) => {
+                    if d != 0 {
+                        multikey_inner(&mut bucket, self.depth + 1);
+                    }
+                    out.append(&mut bucket);
+                },
+                Slot::Node(child) => child.collect(out),
+            }
+        }
+    }
+}
+
+/// Burstsort
+///
+/// > 突发排序, 将字符串依次插入一棵 burst trie 中,
+/// > trie 的叶子是未排序的桶, 当桶中的字符串超过 [`BURST_LIMIT`] 时,
+/// > 桶将按其中字符串的下一个不同的字节分裂 (burst) 为一个节点.
+/// > 最后按顺序遍历 trie, 用多键快速排序排序每个桶
+/// >
+/// > 插入时只访问少量的 trie 节点, 桶较小, 对缓存较为友好, 不稳定
+///
+/// trie 的路径是压缩的, 长的公共前缀不会产生很深的 trie
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::burstsort;
+/// let mut arr = [&b"dab"[..], b"add", b"cab", b"ad", b"fad", b"bed"];
+/// burstsort(&mut arr);
+/// assert_eq!(arr, [&b"ad"[..], b"add", b"bed", b"cab", b"dab", b"fad"]);
+/// ```
+pub fn burstsort<T: AsRef<[u8]>>(arr: &mut [T]) {
+    sort_by_refs(arr, |refs| {
+        let mut root = Node::new(b"", 0);
+        for &s in &*refs {
+            root.insert(s);
+        }
+        let mut out = Vec::with_capacity(refs.len());
+        root.collect(&mut out);
+        refs.copy_from_slice(&out);
+    })
+}
+
+/// Merge sorted `arr[..len]` and `arr[len..]`, and their LCP arrays
+///
+/// > 带有 LCP 的归并, 类似 [`normal_merge`], `lcp[k]`为`arr[k-1]`与`arr[k]`的
+/// > 最长公共前缀长度, `lcp[0]`与`lcp[len]`将被忽略
+/// >
+/// > 归并时记录两侧当前字符串与上一个输出的字符串的公共前缀长度,
+/// > 二者不同时公共前缀更长的一侧更小, 无需比较;
+/// > 相同时从该长度开始比较, 所以已知相同的前缀不会被再次比较
+///
+/// 归并后`lcp`为归并结果的 LCP 数组, 且`lcp[0] = 0`
+///
+/// [`normal_merge`]: crate::normal::normal_merge
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::string_merge;
+/// let mut arr = ["ab", "abc", "b", "a", "abd", "bc"];
+/// let mut lcp = [0, 2, 0, 0, 1, 0];
+/// string_merge(&mut arr, &mut lcp, 3, &mut vec![], &mut vec![]);
+/// assert_eq!(arr, ["a", "ab", "abc", "abd", "b", "bc"]);
+/// assert_eq!(lcp, [0, 1, 2, 2, 0, 1]);
+/// ```
+pub fn string_merge<T>(
+    arr: &mut [T],
+    lcp: &mut [usize],
+    len: usize,
+    buf: &mut Vec<T>,
+    lcp_buf: &mut Vec<usize>,
+)
+where T: AsRef<[u8]> + Default,
+{
+    assert_eq!(arr.len(), lcp.len());
+    lcp_buf.clear();
+    let mut st = NormalMergeState::new(arr, buf, len);
+    // 两侧当前字符串与上一个输出的字符串的公共前缀长度
+    let (mut ha, mut hb) = (0, 0);
+    while let Some((a, b)) = st.heads() {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let take_a = match ha.cmp(&hb) {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            Ordering::Equal => {
+                let h = lcp_from(a, b, ha);
+                let take_a = a.get(h) <= b.get(h);
+                // 另一侧与新的上一个输出的公共前缀长度
+                if take_a { hb = h } else { ha = h }
+                take_a
+            },
+        };
+        let (i, j) = st.pos();
+        if take_a {
+            lcp_buf.push(ha);
+            ha = if i + 1 < len { lcp[i + 1] } else { 0 };
+        } else {
+            lcp_buf.push(hb);
+            hb = if j + 1 < lcp.len() { lcp[j + 1] } else { 0 };
+        }
+        st.take(take_a);
+    }
+
+    let (lo, hi) = st.finish();
+    if !lo.is_empty() {
+        lcp_buf.push(ha);
+        lcp_buf.extend(&lcp[lo.start+1..lo.end]);
+    }
+    if !hi.is_empty() {
+        lcp_buf.push(hb);
+        lcp_buf.extend(&lcp[hi.start+1..hi.end]);
+    }
+
+    lcp.copy_from_slice(lcp_buf);
+    if let Some(first) = lcp.first_mut() {
+        *first = 0
+    }
+}
+
+fn string_merge_sort_inner<T>(
+    arr: &mut [T],
+    lcp: &mut [usize],
+    buf: &mut Vec<T>,
+    lcp_buf: &mut Vec<usize>,
+)
+where T: AsRef<[u8]> + Default,
+{
+    if arr.len() < 2 {
+        lcp.fill(0);
+        return;
+    }
+    let mid = arr.len() >> 1;
+    string_merge_sort_inner(&mut arr[..mid], &mut lcp[..mid], buf, lcp_buf);
+    string_merge_sort_inner(&mut arr[mid..], &mut lcp[mid..], buf, lcp_buf);
+
+    string_merge(arr, lcp, mid, buf, lcp_buf)
+}
+
+/// String Merge Sort, and return the LCP array of result
+///
+/// > 带有 LCP 的字符串归并排序, 使用 [`string_merge`] 归并,
+/// > 归并时通过 LCP 跳过已知相同的前缀的比较,
+/// > 排序的同时得到结果的 LCP 数组, 即`lcp[k]`为`arr[k-1]`与`arr[k]`的
+/// > 最长公共前缀长度, `lcp[0] = 0`
+///
+/// **is stable sort**
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::string_merge_sort_lcp;
+/// let mut arr = ["banana", "band", "ban", "apple", "band"];
+/// let lcp = string_merge_sort_lcp(&mut arr);
+/// assert_eq!(arr, ["apple", "ban", "banana", "band", "band"]);
+/// assert_eq!(lcp, [0, 0, 3, 3, 4]);
+/// ```
+pub fn string_merge_sort_lcp<T>(arr: &mut [T]) -> Vec<usize>
+where T: AsRef<[u8]> + Default,
+{
+    let mut lcp = vec![0; arr.len()];
+    let mut buf = Vec::with_capacity(arr.len());
+    let mut lcp_buf = Vec::with_capacity(arr.len());
+    string_merge_sort_inner(arr, &mut lcp, &mut buf, &mut lcp_buf);
+    lcp
+}
+
+/// String Merge Sort
+///
+/// > 类似 [`string_merge_sort_lcp`], 但是不返回 LCP 数组
+///
+/// **is stable sort**
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::string_merge_sort;
+/// let mut arr = vec![b"dab".to_vec(), b"add".to_vec(), b"cab".to_vec(), b"ad".to_vec()];
+/// string_merge_sort(&mut arr);
+/// assert_eq!(arr, [&b"ad"[..], b"add", b"cab", b"dab"]);
+/// ```
+pub fn string_merge_sort<T>(arr: &mut [T])
+where T: AsRef<[u8]> + Default,
+{
+    string_merge_sort_lcp(arr);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths() -> Vec<String> {
+        let logs = (0..6000usize).map(|i| {
+            let dir = ["", "app/", "app/worker/", "app/worker/logs/"][i % 4];
+            format!("/var/log/{dir}{}.log", i * 7919 % 1500)
+        });
+        // 在已经分裂的桶的公共前缀中分叉
+        let others = (0..600usize).map(|i| {
+            ["/var/lib/", "/var/", "/usr/", "/var/log"][i % 4].repeat(i % 3 + 1)
+        });
+        logs.chain(others).collect()
+    }
+
+    #[test]
+    fn string_sorts_test() {
+        let sorts: [fn(&mut [String]); 3] = [
+            multikey_quicksort,
+            msd_string_sort,
+            burstsort,
+        ];
+        for sort in sorts {
+            let mut arr = paths();
+            let mut expected = arr.clone();
+            expected.sort();
+            sort(&mut arr);
+            assert_eq!(arr, expected);
+
+            // 相同的长字符串
+            let mut arr = vec!["x".repeat(2000); 3000];
+            sort(&mut arr);
+            assert!(arr.iter().all(|s| s.len() == 2000));
+        }
+    }
+
+    #[test]
+    fn string_merge_sort_lcp_test() {
+        let mut arr = paths();
+        let mut expected = arr.clone();
+        expected.sort();
+        let lcp = string_merge_sort_lcp(&mut arr);
+        assert_eq!(arr, expected);
+        assert_eq!(lcp[0], 0);
+        for (k, &h) in lcp.iter().enumerate().skip(1) {
+            assert_eq!(h, lcp_from(arr[k-1].as_bytes(), arr[k].as_bytes(), 0), "{k}");
+        }
+    }
+
+    #[test]
+    fn lcp_insert_sort_test() {
+        let strs = ["ab", "", "abc", "b", "ab", "a", "abd", "abca", "aa", "abc"];
+        let mut refs = zip(strs.map(str::as_bytes), 0..).collect::<Vec<_>>();
+        lcp_insert_sort(&mut refs, 0);
+        let mut expected = zip(strs.map(str::as_bytes), 0..).collect::<Vec<_>>();
+        expected.sort_by_key(|&(s, _)| s);
+        assert_eq!(refs, expected);
+    }
+}
@@ -0,0 +1,343 @@
+//! 后缀数组, 排序作为基础的常见应用
+//!
+//! 后缀数组`sa`是字符串所有后缀的起始位置按后缀的字典序排序的结果,
+//! 较短的后缀是较长后缀的前缀时较短的更小
+
+use std::mem;
+
+use crate::normal::merge_sort;
+
+use super::{counting_sort_by_key, radix_sort_by_key};
+
+/// Suffix array, naive comparison sort
+///
+/// > 直接使用 [`merge_sort`] 比较后缀排序, 每次比较最坏`O(n)`,
+/// > 最坏复杂度`O(n^2*log(n))`, 在重复较多的字符串上很慢
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::suffix_array_naive;
+/// assert_eq!(suffix_array_naive(b"banana"), [5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array_naive(s: &[u8]) -> Vec<usize> {
+    let mut sa = (0..s.len()).collect::<Vec<_>>();
+    merge_sort(&mut sa, |&a, &b| s[a..] < s[b..]);
+    sa
+}
+
+/// Suffix array, prefix doubling (Manber–Myers)
+///
+/// > 倍增法, 先按第一个字节计数排序, 之后每轮已知所有后缀按前`k`个字节的排名,
+/// > 以`(rank[i], rank[i+k])`为键用 [`radix_sort_by_key`] 排序,
+/// > 即得到按前`2k`个字节的排名, 直到所有排名都不同
+/// >
+/// > 最多`log(n)`轮, 每轮`O(n)`, 复杂度`O(n*log(n))`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::suffix_array_doubling;
+/// assert_eq!(suffix_array_doubling(b"banana"), [5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array_doubling(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa = (0..n).collect::<Vec<_>>();
+    counting_sort_by_key(&mut sa, 0..256, |&i| s[i].into());
+
+    // 排名从 1 开始, 0 表示超出字符串的空后缀
+    let mut rank = vec![0; n];
+    let mut distinct = rerank(&sa, &mut rank, |i| (s[i].into(), 0));
+
+    let mut k = 1;
+    let mut next = vec![0; n];
+    while distinct < n {
+        let key = |i: usize| (rank[i], rank.get(i + k).copied().unwrap_or(0));
+        radix_sort_by_key(&mut sa, |&i| key(i));
+        distinct = rerank(&sa, &mut next, key);
+        mem::swap(&mut rank, &mut next);
+        k <<= 1;
+    }
+    sa
+}
+
+/// 按已排序的`sa`中相邻键是否相同重新计算排名, 返回不同排名的数量
+fn rerank<F>(sa: &[usize], rank: &mut [usize], key: F) -> usize
+where F: Fn(usize) -> (usize, usize),
+{
+    let mut r = 0;
+    for (k, &i) in sa.iter().enumerate() {
+        if k == 0 || key(sa[k-1]) != key(i) {
+            r += 1;
+        }
+        rank[i] = r;
+    }
+    r
+}
+
+/// SA-IS 对整数字符串构造后缀数组, 字符在`0..=upper`中
+fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => (),
+    }
+
+    // ls[i]: 后缀 i 是否为 S 型, 即 s[i..] < s[i+1..]
+    let mut ls = vec![false; n];
+    for i in (0..n-1).rev() {
+        ls[i] = if s[i] == s[i+1] { ls[i+1] } else { s[i] < s[i+1] };
+    }
+
+    // 每个字符的桶中, L 型后缀与 S 型后缀的起始位置
+    let mut sum_l = vec![0; upper + 1];
+    let mut sum_s = vec![0; upper + 1];
+    for i in 0..n {
+        if ls[i] {
+            sum_l[s[i] + 1] += 1;
+        } else {
+            sum_s[s[i]] += 1;
+        }
+    }
+    for c in 0..=upper {
+        sum_s[c] += sum_l[c];
+        if c < upper {
+            sum_l[c+1] += sum_s[c];
+        }
+    }
+
+    // 由排好序的 LMS 后缀诱导排序所有后缀
+    let induce = |sa: &mut [usize], lms: &[usize]| {
+        sa.fill(usize::MAX);
+        let mut buf = sum_s.clone();
+        for &d in lms {
+            sa[buf[s[d]]] = d;
+            buf[s[d]] += 1;
+        }
+        buf.copy_from_slice(&sum_l);
+        sa[buf[s[n-1]]] = n - 1;
+        buf[s[n-1]] += 1;
+        for k in 0..n {
+            let v = sa[k];
+            if v != usize::MAX && v >= 1 && !ls[v-1] {
+                sa[buf[s[v-1]]] = v - 1;
+                buf[s[v-1]] += 1;
+            }
+        }
+        buf.copy_from_slice(&sum_l);
+        for k in (0..n).rev() {
+            let v = sa[k];
+            if v != usize::MAX && v >= 1 && ls[v-1] {
+                buf[s[v-1] + 1] -= 1;
+                sa[buf[s[v-1] + 1]] = v - 1;
+            }
+        }
+    };
+
+    // LMS: 左侧为 L 型的 S 型后缀
+    let lms = (1..n).filter(|&i| !ls[i-1] && ls[i]).collect::<Vec<_>>();
+    let mut lms_map = vec![usize::MAX; n];
+    for (k, &i) in lms.iter().enumerate() {
+        lms_map[i] = k;
+    }
+
+    let mut sa = vec![0; n];
+    induce(&mut sa, &lms);
+    if lms.is_empty() { return sa }
+
+    // 诱导排序后 LMS 子串已经有序, 为其命名, 相同的子串名称相同
+    let m = lms.len();
+    let sorted_lms = sa.iter()
+        .copied()
+        .filter(|&v| lms_map[v] != usize::MAX)
+        .collect::<Vec<_>>();
+    let end = |i: usize| lms.get(lms_map[i] + 1).copied().unwrap_or(n);
+    let mut rec_s = vec![0; m];
+    let mut rec_upper = 0;
+    for w in sorted_lms.windows(2) {
+        let (l, r) = (w[0], w[1]);
+        let (end_l, end_r) = (end(l), end(r));
+        let same = end_l - l == end_r - r
+            && s[l..end_l] == s[r..end_r]
+            && end_l < n && end_r < n
+            && s[end_l] == s[end_r];
+        if !same {
+            rec_upper += 1;
+        }
+        rec_s[lms_map[r]] = rec_upper;
+    }
+
+    // 递归排序 LMS 后缀, 再以其诱导排序
+    let rec_sa = sa_is(&rec_s, rec_upper);
+    let sorted_lms = rec_sa.into_iter().map(|k| lms[k]).collect::<Vec<_>>();
+    induce(&mut sa, &sorted_lms);
+    sa
+}
+
+/// Suffix array, SA-IS
+///
+/// > 诱导排序 (SA-IS), 将后缀分为 L 型与 S 型,
+/// > 由 LMS 后缀 (左侧为 L 型的 S 型后缀) 的顺序可以通过两次扫描诱导出所有后缀的顺序,
+/// > 而 LMS 后缀的顺序通过将 LMS 子串命名后递归构造后缀数组得到,
+/// > 递归的规模最多为一半, 复杂度`O(n)`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::suffix_array;
+/// assert_eq!(suffix_array(b"banana"), [5, 3, 1, 0, 4, 2]);
+/// assert_eq!(suffix_array(b"aaaa"), [3, 2, 1, 0]);
+/// ```
+pub fn suffix_array(s: &[u8]) -> Vec<usize> {
+    let s = s.iter().map(|&b| b.into()).collect::<Vec<usize>>();
+    sa_is(&s, u8::MAX.into())
+}
+
+/// LCP array of suffix array (Kasai)
+///
+/// > 计算后缀数组中相邻后缀的最长公共前缀长度, 即`lcp[k]`为后缀`sa[k-1]`与`sa[k]`的
+/// > 最长公共前缀长度, `lcp[0] = 0`
+/// >
+/// > 按原字符串的顺序处理后缀, 后缀`i+1`的 LCP 至少为后缀`i`的 LCP 减一,
+/// > 所以总共只需比较`O(n)`次
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::{lcp_array, suffix_array};
+/// let sa = suffix_array(b"banana");
+/// assert_eq!(lcp_array(b"banana", &sa), [0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array(s: &[u8], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    assert_eq!(n, sa.len());
+    let mut rank = vec![0; n];
+    for (k, &i) in sa.iter().enumerate() {
+        rank[i] = k;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut h = 0usize;
+    for i in 0..n {
+        if rank[i] == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + h < n && j + h < n && s[i+h] == s[j+h] {
+            h += 1;
+        }
+        lcp[rank[i]] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Burrows–Wheeler transform
+///
+/// > 对`s`末尾加上一个最小的结束符`$`后, 将其所有循环移位排序, 取每一行的最后一个字节.
+/// > 相同的上下文前的字节会聚集在一起, 更易于压缩
+///
+/// 返回不含结束符的结果, 以及结束符所在的行
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::{bwt, suffix_array};
+/// let sa = suffix_array(b"banana");
+/// // annb$aa
+/// assert_eq!(bwt(b"banana", &sa), (b"annbaa".to_vec(), 4));
+/// ```
+pub fn bwt(s: &[u8], sa: &[usize]) -> (Vec<u8>, usize) {
+    assert_eq!(s.len(), sa.len());
+    let Some(&last) = s.last() else { return (vec![], 0) };
+
+    // 第 0 行从结束符开始, 其余每行对应一个后缀
+    let mut out = Vec::with_capacity(s.len());
+    out.push(last);
+    let mut primary = 0;
+    for (k, &i) in sa.iter().enumerate() {
+        match i {
+            0 => primary = k + 1,
+            i => out.push(s[i-1]),
+        }
+    }
+    (out, primary)
+}
+
+/// Inverse of [`bwt`]
+///
+/// > 通过 LF 映射还原, 第`r`行最后一个字节在第一列中对应的行即为其前一个循环移位
+///
+/// # Example
+/// ```
+/// # use sorts_rs::other::inverse_bwt;
+/// assert_eq!(inverse_bwt(b"annbaa", 4), b"banana");
+/// ```
+pub fn inverse_bwt(last: &[u8], primary: usize) -> Vec<u8> {
+    let n = last.len();
+    if n == 0 { return vec![] }
+    assert!(primary > 0 && primary <= n, "invalid primary index {primary}");
+
+    // 完整的最后一列, 结束符为 0, 字节 b 为 b+1
+    let column = last[..primary].iter().map(|&b| usize::from(b) + 1)
+        .chain([0])
+        .chain(last[primary..].iter().map(|&b| usize::from(b) + 1))
+        .collect::<Vec<_>>();
+    let mut starts = [0usize; 257];
+    for &c in &column {
+        starts[c] += 1;
+    }
+    let mut sum = 0;
+    for start in &mut starts {
+        (*start, sum) = (sum, sum + *start);
+    }
+    let lf = column.iter()
+        .map(|&c| {
+            starts[c] += 1;
+            starts[c] - 1
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = vec![0; n];
+    let mut row = 0;
+    for ele in out.iter_mut().rev() {
+        *ele = (column[row] - 1) as u8;
+        row = lf[row];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        let binary = (0..=10).flat_map(|len| {
+            (0..1u32 << len).map(move |bits| {
+                (0..len).map(|i| b'a' + (bits >> i & 1) as u8).collect()
+            })
+        });
+        let pseudo_random = (0..200).map(|len| {
+            (0..len).map(|i: usize| b"abcab\0\xff"[(i * i * 31 + i * len + len) % 7]).collect()
+        });
+        let repeats = [b"abcabcabcabcabca".repeat(20), b"aaaa".repeat(100)];
+        binary.chain(pseudo_random).chain(repeats)
+    }
+
+    #[test]
+    fn suffix_array_test() {
+        for s in inputs() {
+            let sa = suffix_array_naive(&s);
+            assert_eq!(suffix_array_doubling(&s), sa, "{s:?}");
+            assert_eq!(suffix_array(&s), sa, "{s:?}");
+
+            let lcp = lcp_array(&s, &sa);
+            for k in 1..sa.len() {
+                let (a, b) = (&s[sa[k-1]..], &s[sa[k]..]);
+                let h = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+                assert_eq!(lcp[k], h, "{s:?}");
+            }
+
+            let (last, primary) = bwt(&s, &sa);
+            assert_eq!(inverse_bwt(&last, primary), s);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! 排列相关的工具, 如按一个数组排序多个平行数组
+//!
+//! 排列`perm`总是表示取出的位置, 即结果的第`i`个元素是原来的第`perm[i]`个
+
+use crate::{normal::merge_sort, registry::LtSortFn};
+
+/// Indices that sort `arr`
+///
+/// > 返回稳定排序后的下标, 即`arr[idx[0]], arr[idx[1]], ..`有序,
+/// > 相等的元素保持原来的顺序, 基于 [`merge_sort`], 不修改`arr`
+///
+/// **is stable sort**
+///
+/// # Example
+/// ```
+/// # use sorts_rs::permutation::argsort;
+/// assert_eq!(argsort(&[30, 10, 20, 10], i32::lt), [1, 3, 2, 0]);
+/// ```
+pub fn argsort<T, F>(arr: &[T], mut lt: F) -> Vec<usize>
+where F: FnMut(&T, &T) -> bool,
+{
+    let mut idx = (0..arr.len()).collect::<Vec<_>>();
+    merge_sort(&mut idx, |&a, &b| lt(&arr[a], &arr[b]));
+    idx
+}
+
+/// Reorder `arr` so that `arr[i]` becomes the old `arr[perm[i]]`
+///
+/// > 沿着置换环交换, 不需要额外空间, 结束后`perm`将变为`0..n`
+///
+/// # Panics
+/// - `arr.len() != perm.len()`
+/// - `perm`不是`0..n`的排列, 此时`arr`已经被部分重排
+///
+/// # Example
+/// ```
+/// # use sorts_rs::permutation::{apply_permutation_in_place, argsort};
+/// let mut names = ["c", "a", "b"];
+/// let mut perm = argsort(&names, |a, b| a < b);
+/// apply_permutation_in_place(&mut names, &mut perm);
+/// assert_eq!(names, ["a", "b", "c"]);
+/// assert_eq!(perm, [0, 1, 2]);
+/// ```
+pub fn apply_permutation_in_place<T>(arr: &mut [T], perm: &mut [usize]) {
+    assert_eq!(arr.len(), perm.len(), "permutation length mismatch");
+    for i in 0..perm.len() {
+        // 已经处理的位置都被设为自身, 再次经过时说明不是排列
+        let mut cur = i;
+        while perm[cur] != i {
+            let next = perm[cur];
+            assert_ne!(next, cur, "not a permutation");
+            arr.swap(cur, next);
+            perm[cur] = cur;
+            cur = next;
+        }
+        perm[cur] = cur;
+    }
+}
+
+/// Inverse of permutation
+///
+/// > 返回`inv`使得`inv[perm[i]] == i`,
+/// > 对 [`argsort`] 的结果求逆即得到每个元素排序后的位置
+///
+/// # Panics
+/// `perm`不是`0..n`的排列
+///
+/// # Example
+/// ```
+/// # use sorts_rs::permutation::invert_permutation;
+/// assert_eq!(invert_permutation(&[2, 0, 1]), [1, 2, 0]);
+/// ```
+pub fn invert_permutation(perm: &[usize]) -> Vec<usize> {
+    let mut inv = vec![usize::MAX; perm.len()];
+    for (i, &p) in perm.iter().enumerate() {
+        assert!(inv.get(p) == Some(&usize::MAX), "not a permutation");
+        inv[p] = i;
+    }
+    inv
+}
+
+/// How [`rank`] handles ties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ties {
+    /// 相等的元素名次相同, 名次连续, 如`[1, 2, 2, 3]`
+    Dense,
+    /// 相等的元素都取最小的名次, 如`[1, 2, 2, 4]`
+    Min,
+    /// 相等的元素都取名次的平均值, 如`[1, 2.5, 2.5, 4]`
+    Average,
+}
+
+/// Rank of each element, starting from `1`
+///
+/// > 排序后的名次, 从`1`开始, 相等的元素按`ties`处理.
+/// > 为了容纳 [`Ties::Average`] 的结果, 名次均为`f64`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::permutation::{rank, Ties};
+/// let arr = [30, 10, 20, 10];
+/// assert_eq!(rank(&arr, Ties::Dense, i32::lt), [3.0, 1.0, 2.0, 1.0]);
+/// assert_eq!(rank(&arr, Ties::Min, i32::lt), [4.0, 1.0, 3.0, 1.0]);
+/// assert_eq!(rank(&arr, Ties::Average, i32::lt), [4.0, 1.5, 3.0, 1.5]);
+/// ```
+pub fn rank<T, F>(arr: &[T], ties: Ties, mut lt: F) -> Vec<f64>
+where F: FnMut(&T, &T) -> bool,
+{
+    let idx = argsort(arr, &mut lt);
+    let mut ranks = vec![0.0; arr.len()];
+    let (mut start, mut dense) = (0, 0);
+    while start < idx.len() {
+        // idx[start..end] 是一组相等的元素
+        let mut end = start + 1;
+        while end < idx.len() && !lt(&arr[idx[start]], &arr[idx[end]]) {
+            end += 1;
+        }
+        dense += 1;
+        let r = match ties {
+            Ties::Dense => dense as f64,
+            Ties::Min => (start + 1) as f64,
+            Ties::Average => (start + 1 + end) as f64 / 2.0,
+        };
+        for &i in &idx[start..end] {
+            ranks[i] = r;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Sort `keys` and `values` together by `keys`
+///
+/// > 按`keys`稳定排序, 并以相同的方式重排`values`,
+/// > 只需要下标的辅助空间, 不需要构造`(key, value)`的元组
+///
+/// **is stable sort**
+///
+/// # Panics
+/// `keys.len() != values.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::permutation::co_sort;
+/// let mut ages = [30, 10, 20, 10];
+/// let mut names = ["carol", "alice", "bob", "dave"];
+/// co_sort(&mut ages, &mut names, i32::lt);
+/// assert_eq!(ages, [10, 10, 20, 30]);
+/// assert_eq!(names, ["alice", "dave", "bob", "carol"]);
+/// ```
+pub fn co_sort<K, V, F>(keys: &mut [K], values: &mut [V], lt: F)
+where F: FnMut(&K, &K) -> bool,
+{
+    assert_eq!(keys.len(), values.len(), "keys and values length mismatch");
+    let mut perm = argsort(keys, lt);
+    let mut perm2 = perm.clone();
+    apply_permutation_in_place(keys, &mut perm);
+    apply_permutation_in_place(values, &mut perm2);
+}
+
+/// Sort by key, computing each key only once (Schwartzian transform)
+///
+/// > 键的计算代价较大时 (如解析, 哈希, 规范化), 直接在`lt`中计算会使每次比较都重复计算.
+/// > 这里先将每个元素的键与下标计算到`(K, usize)`的缓冲中,
+/// > 使用`algorithm`按`(键, 下标)`排序缓冲, 再沿置换环原地重排`arr`
+/// >
+/// > 由于下标参与比较, 不存在相等的元素, 所以即使`algorithm`不稳定, 结果也是稳定的
+///
+/// `algorithm`可以是 [`registry`](crate::registry) 中已注册的任何排序
+///
+/// **is stable sort**
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{permutation::sort_by_cached_key, registry};
+/// let mut words = ["Banana", "apple", "cherry", "Apple"];
+/// let quick_sort = registry::find("quick_sort").unwrap();
+/// sort_by_cached_key(&mut words, |w| w.to_lowercase(), quick_sort.sort);
+/// assert_eq!(words, ["apple", "Apple", "Banana", "cherry"]);
+/// ```
+pub fn sort_by_cached_key<T, K, F>(
+    arr: &mut [T],
+    mut f: F,
+    algorithm: LtSortFn<(K, usize)>,
+)
+where F: FnMut(&T) -> K,
+      K: Ord,
+{
+    let mut keys = arr.iter()
+        .enumerate()
+        .map(|(i, ele)| (f(ele), i))
+        .collect::<Vec<_>>();
+    algorithm(&mut keys, &mut <(K, usize)>::lt);
+    let mut perm = keys.into_iter()
+        .map(|(_, i)| i)
+        .collect::<Vec<_>>();
+    apply_permutation_in_place(arr, &mut perm);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, panic::catch_unwind};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::registry;
+
+    /// 长度与取值范围各异的输入, 大多包含重复
+    fn inputs() -> impl Iterator<Item = Vec<usize>> {
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..100).map(move |k| {
+            let len = k * 3;
+            let max = [2, len.max(1), usize::MAX >> 1][k % 3];
+            (0..len).map(|_| rng.gen_range(0..max)).collect()
+        })
+    }
+
+    #[test]
+    fn argsort_test() {
+        for input in inputs() {
+            let n = input.len();
+            let mut expected = (0..n).collect::<Vec<_>>();
+            expected.sort_by_key(|&i| input[i]);
+            let perm = argsort(&input, usize::lt);
+            assert_eq!(perm, expected, "{input:?}");
+
+            let inv = invert_permutation(&perm);
+            assert!((0..n).all(|i| inv[perm[i]] == i), "{perm:?} {inv:?}");
+
+            let mut sorted = input.clone();
+            sorted.sort();
+            let mut output = input.clone();
+            let mut applied = perm.clone();
+            apply_permutation_in_place(&mut output, &mut applied);
+            assert_eq!(output, sorted);
+            assert_eq!(applied, (0..n).collect::<Vec<_>>());
+
+            let mut keys = input.clone();
+            let mut values = (0..n).collect::<Vec<_>>();
+            co_sort(&mut keys, &mut values, usize::lt);
+            assert_eq!(keys, sorted);
+            assert_eq!(values, perm);
+        }
+    }
+
+    #[test]
+    fn rank_test() {
+        for input in inputs() {
+            let ranks = [Ties::Dense, Ties::Min, Ties::Average]
+                .map(|ties| rank(&input, ties, usize::lt));
+            for (i, &x) in input.iter().enumerate() {
+                let less = input.iter().filter(|&&y| y < x).count();
+                let equal = input.iter().filter(|&&y| y == x).count();
+                let mut distinct = input.iter().filter(|&&y| y < x).collect::<Vec<_>>();
+                distinct.sort();
+                distinct.dedup();
+                let expected = [
+                    distinct.len() as f64 + 1.0,
+                    less as f64 + 1.0,
+                    less as f64 + (equal as f64 + 1.0) / 2.0,
+                ];
+                assert_eq!(ranks.each_ref().map(|r| r[i]), expected, "{input:?} at {i}");
+            }
+        }
+    }
+
+    /// 任何已注册的排序都得到稳定的结果, 且每个元素只计算一次键
+    #[test]
+    fn sort_by_cached_key_test() {
+        for algo in registry::lt_sorts::<(usize, usize)>() {
+            for input in inputs() {
+                let mut output = input.iter().copied()
+                    .zip(0..)
+                    .collect::<Vec<_>>();
+                let mut expected = output.clone();
+                expected.sort_by_key(|&(n, _)| n % 16);
+                let calls = Cell::new(0);
+                sort_by_cached_key(&mut output, |&(n, _)| {
+                    calls.set(calls.get() + 1);
+                    n % 16
+                }, algo.sort);
+                assert_eq!(calls.get(), input.len(), "{}", algo.name);
+                assert_eq!(output, expected, "{}", algo.name);
+            }
+        }
+    }
+
+    /// 不是排列时 panic, 而不是无限循环
+    #[test]
+    fn invalid_permutation_test() {
+        for perm in [[1, 1, 2], [0, 2, 2], [2, 0, 0], [0, 1, 3]] {
+            let apply = catch_unwind(|| {
+                apply_permutation_in_place(&mut [0; 3], &mut perm.clone())
+            });
+            assert!(apply.is_err(), "{perm:?}");
+            assert!(catch_unwind(|| invert_permutation(&perm)).is_err(), "{perm:?}");
+        }
+    }
+}
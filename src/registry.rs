@@ -0,0 +1,90 @@
+//! 已注册的比较排序, 方便测试与基准测试等统一遍历所有排序
+//!
+//! 排序函数被擦除为函数指针, 比较函数使用`&mut dyn FnMut`传入
+
+use crate::*;
+
+/// Type erased sort using `lt` compare
+pub type LtSortFn<T> = fn(&mut [T], &mut dyn FnMut(&T, &T) -> bool);
+
+/// A registered sort
+#[derive(Debug)]
+pub struct Algorithm<T> {
+    /// Path in this crate, e.g. `normal::merge_sort`
+    pub name: &'static str,
+    pub sort: LtSortFn<T>,
+    /// 平均复杂度为`O(n^2)`, 不适用于较大数据量
+    pub quadratic: bool,
+}
+impl<T> Clone for Algorithm<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for Algorithm<T> { }
+
+impl<T> Algorithm<T> {
+    /// Name without module path, e.g. `merge_sort`
+    pub fn short_name(&self) -> &'static str {
+        self.name.rsplit("::").next().unwrap()
+    }
+}
+
+macro_rules! algorithms {
+    ($($path:path => $quadratic:expr),+ $(,)?) => {
+        vec![
+            $(
+                Algorithm {
+                    name: stringify!($path),
+                    sort: |arr, lt| $path(arr, lt),
+                    quadratic: $quadratic,
+                },
+            )+
+        ]
+    };
+}
+
+/// All registered sorts using `lt` compare
+///
+/// # Example
+/// ```
+/// # use sorts_rs::registry::lt_sorts;
+/// for algo in lt_sorts() {
+///     let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+///     (algo.sort)(&mut arr, &mut i32::lt);
+///     assert_eq!(arr, [0, 1, 2, 3, 4, 5, 6, 9], "{}", algo.name);
+/// }
+/// ```
+pub fn lt_sorts<T: Default>() -> Vec<Algorithm<T>> {
+    algorithms![
+        basic::bubble_sort => true,
+        basic::cocktail_sort => true,
+        basic::insert_sort => true,
+        basic::binary_insert_sort => true,
+        basic::select_sort => true,
+        basic::select_doubled_sort => true,
+        normal::comb_sort => false,
+        normal::shell_sort => false,
+        normal::sedgewick_sort => false,
+        normal::quick_sort => false,
+        normal::merge_sort => false,
+        normal::heap_sort => false,
+        other::bitonic_sort_any => false,
+        other::odd_even_merge_sort => false,
+        other::odd_even_transposition_sort => true,
+    ]
+}
+
+/// Find registered sort by full name or short name
+///
+/// # Example
+/// ```
+/// # use sorts_rs::registry::find;
+/// assert_eq!(find::<i32>("merge_sort").unwrap().name, "normal::merge_sort");
+/// assert_eq!(find::<i32>("basic::insert_sort").unwrap().name, "basic::insert_sort");
+/// assert!(find::<i32>("foo_sort").is_none());
+/// ```
+pub fn find<T: Default>(name: &str) -> Option<Algorithm<T>> {
+    lt_sorts().into_iter()
+        .find(|algo| algo.name == name || algo.short_name() == name)
+}
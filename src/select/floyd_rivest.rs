@@ -0,0 +1,100 @@
+use std::cmp::Ordering;
+
+use super::median_of_medians_inner;
+
+/// 长度超过这个值时, 先递归地在样本区间中选择来确定基准
+pub const FLOYD_RIVEST_SAMPLE: usize = 600;
+
+/// `budget`为剩余可以划分的总长度, 包括样本区间中的划分,
+/// 用尽时说明基准选得不好, 对当前区间改用中位数的中位数
+pub(super) fn floyd_rivest_inner<T, F>(
+    arr: &mut [T],
+    mut left: usize,
+    mut right: usize,
+    k: usize,
+    budget: &mut usize,
+    lt: &mut F,
+)
+where F: FnMut(&T, &T) -> bool,
+{
+    while right > left {
+        let len = right - left + 1;
+        if *budget < len {
+            return median_of_medians_inner(&mut arr[left..=right], k - left, lt);
+        }
+        *budget -= len;
+
+        if right - left > FLOYD_RIVEST_SAMPLE {
+            // 在包含 k 的约 n^(2/3) 大小的区间中递归选择, 使 arr[k] 成为很好的基准
+            let n = (right - left + 1) as f64;
+            let i = (k - left + 1) as f64;
+            let z = n.ln();
+            let s = 0.5 * (2.0 * z / 3.0).exp();
+            let sd = 0.5 * (z * s * (n - s) / n).sqrt() * (i - n / 2.0).signum();
+            let new_left = (k as f64 - i * s / n + sd) as usize;
+            let new_right = (k as f64 + (n - i) * s / n + sd) as usize;
+            let new_left = new_left.clamp(left, k);
+            let new_right = new_right.clamp(k, right);
+            floyd_rivest_inner(arr, new_left, new_right, k, budget, lt);
+        }
+
+        // 以 arr[k] 为基准划分 arr[left..=right], 基准放在较小一侧的端点
+        arr.swap(left, k);
+        let p = if lt(&arr[left], &arr[right]) {
+            left
+        } else {
+            arr.swap(left, right);
+            right
+        };
+        let (mut i, mut j) = (left + 1, right - 1);
+        while lt(&arr[i], &arr[p]) { i += 1 }
+        while lt(&arr[p], &arr[j]) { j -= 1 }
+        while i < j {
+            arr.swap(i, j);
+            i += 1;
+            j -= 1;
+            while lt(&arr[i], &arr[p]) { i += 1 }
+            while lt(&arr[p], &arr[j]) { j -= 1 }
+        }
+        if p == left {
+            arr.swap(left, j);
+        } else {
+            j += 1;
+            arr.swap(j, right);
+        }
+
+        match k.cmp(&j) {
+            Ordering::Equal => return,
+            Ordering::Less => right = j - 1,
+            Ordering::Greater => left = j + 1,
+        }
+    }
+}
+
+/// Floyd–Rivest select
+///
+/// > 在包含`k`的一个较小样本区间中递归选择, 使基准非常接近第`k`小,
+/// > 划分后剩余的区间很小, 期望比较次数约`n + min(k, n-k)`,
+/// > 在较大的数据上比快速选择更快.
+/// > 当划分过的总长度超过`4n`时改用 [`median_of_medians`](super::median_of_medians),
+/// > 从而保证最坏复杂度`O(n)`
+///
+/// # Panics
+/// `k >= arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::floyd_rivest;
+/// let mut arr = (0..10000).map(|i| i * 7919 % 10007).collect::<Vec<_>>();
+/// let mut sorted = arr.clone();
+/// sorted.sort();
+/// floyd_rivest(&mut arr, 1234, i32::lt);
+/// assert_eq!(arr[1234], sorted[1234]);
+/// ```
+pub fn floyd_rivest<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k < arr.len(), "index {k} out of range for length {}", arr.len());
+    let mut budget = 4 * arr.len();
+    floyd_rivest_inner(arr, 0, arr.len() - 1, k, &mut budget, &mut lt)
+}
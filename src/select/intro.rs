@@ -0,0 +1,154 @@
+use std::mem;
+
+use crate::{basic::insert_sort, normal::partition};
+
+/// 小于这个长度时直接插入排序
+pub const SELECT_INSERT_CUTOFF: usize = 16;
+
+/// 三路划分, 返回`(a, b)`,
+/// 使得`arr[..a]`均小于基准, `arr[a..b]`均等于基准, `arr[b..]`均大于基准
+fn partition3<T, F>(arr: &mut [T], pivot: usize, lt: &mut F) -> (usize, usize)
+where F: FnMut(&T, &T) -> bool,
+{
+    arr.swap(0, pivot);
+    // arr[1..lo] < 基准, arr[lo..i] == 基准, arr[hi..] > 基准
+    let (mut lo, mut i, mut hi) = (1, 1, arr.len());
+    while i < hi {
+        if lt(&arr[i], &arr[0]) {
+            arr.swap(lo, i);
+            lo += 1;
+            i += 1;
+        } else if lt(&arr[0], &arr[i]) {
+            hi -= 1;
+            arr.swap(i, hi);
+        } else {
+            i += 1;
+        }
+    }
+    arr.swap(0, lo-1);
+    (lo-1, hi)
+}
+
+/// 每五个一组, 将各组中位数移至开头, 再递归选出其中位数, 返回其位置
+///
+/// 至少有约`3/10`的元素不大于它, 也至少有约`3/10`的元素不小于它
+fn pivot_of_medians<T, F>(arr: &mut [T], lt: &mut F) -> usize
+where F: FnMut(&T, &T) -> bool,
+{
+    let groups = arr.len() / 5;
+    for g in 0..groups {
+        insert_sort(&mut arr[g*5..g*5+5], &mut *lt);
+        arr.swap(g, g*5+2);
+    }
+    median_of_medians_inner(&mut arr[..groups], groups/2, lt);
+    groups / 2
+}
+
+pub(super) fn median_of_medians_inner<T, F>(mut arr: &mut [T], mut k: usize, lt: &mut F)
+where F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        if arr.len() <= SELECT_INSERT_CUTOFF {
+            insert_sort(arr, &mut *lt);
+            return;
+        }
+        let pivot = pivot_of_medians(arr, lt);
+        let (a, b) = partition3(arr, pivot, lt);
+        if k < a {
+            arr = &mut mem::take(&mut arr)[..a];
+        } else if k >= b {
+            arr = &mut mem::take(&mut arr)[b..];
+            k -= b;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Median of medians select
+///
+/// > 中位数的中位数 (BFPRT), 每五个一组取中位数, 以这些中位数的中位数为基准,
+/// > 保证每次至少排除约`3/10`的元素, 并用三路划分排除与基准相等的元素,
+/// > 最坏复杂度`O(n)`, 但常数较大
+///
+/// # Panics
+/// `k >= arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::median_of_medians;
+/// let mut arr = [5; 100];
+/// arr[42] = 0;
+/// median_of_medians(&mut arr, 0, i32::lt);
+/// assert_eq!(arr[0], 0);
+/// median_of_medians(&mut arr, 50, i32::lt);
+/// assert_eq!(arr[50], 5);
+/// ```
+pub fn median_of_medians<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k < arr.len(), "index {k} out of range for length {}", arr.len());
+    median_of_medians_inner(arr, k, &mut lt)
+}
+
+/// 将首尾与中间三个元素的中位数移至末尾, 作为 [`partition`] 的基准
+fn median3_to_last<T, F>(arr: &mut [T], lt: &mut F)
+where F: FnMut(&T, &T) -> bool,
+{
+    let (a, b, c) = (0, arr.len() / 2, arr.len() - 1);
+    if lt(&arr[b], &arr[a]) { arr.swap(a, b) }
+    if lt(&arr[c], &arr[b]) { arr.swap(b, c) }
+    if lt(&arr[b], &arr[a]) { arr.swap(a, b) }
+    arr.swap(b, c);
+}
+
+pub(super) fn introselect_inner<T, F>(mut arr: &mut [T], mut k: usize, lt: &mut F)
+where F: FnMut(&T, &T) -> bool,
+{
+    // 划分过的总长度超过 4n 时, 说明基准选得不好, 改用中位数的中位数
+    let mut budget = 4 * arr.len();
+    loop {
+        if arr.len() <= SELECT_INSERT_CUTOFF {
+            insert_sort(arr, &mut *lt);
+            return;
+        }
+        if budget < arr.len() {
+            return median_of_medians_inner(arr, k, lt);
+        }
+        budget -= arr.len();
+
+        median3_to_last(arr, lt);
+        let p = partition(arr, lt);
+        if k < p {
+            arr = &mut mem::take(&mut arr)[..p];
+        } else if k > p {
+            arr = &mut mem::take(&mut arr)[p+1..];
+            k -= p + 1;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Introselect
+///
+/// > 内省选择, 以三数取中的快速选择为主, 平均复杂度`O(n)`,
+/// > 当划分过的总长度超过`4n`时改用 [`median_of_medians`],
+/// > 从而保证最坏复杂度`O(n)`
+///
+/// # Panics
+/// `k >= arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::introselect;
+/// let mut arr = (0..1000).collect::<Vec<_>>();
+/// introselect(&mut arr, 500, i32::gt);
+/// assert_eq!(arr[500], 499);
+/// ```
+pub fn introselect<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k < arr.len(), "index {k} out of range for length {}", arr.len());
+    introselect_inner(arr, k, &mut lt)
+}
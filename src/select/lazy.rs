@@ -0,0 +1,59 @@
+use std::iter::FusedIterator;
+
+use crate::{cmp, normal::BHeap};
+
+/// Iterator of [`lazy_sorted`]
+#[derive(Debug)]
+pub struct LazySorted<'a, T, F> {
+    heap: BHeap<'a, T>,
+    lt: F,
+}
+
+impl<'a, T, F> Iterator for LazySorted<'a, T, F>
+where F: FnMut(&T, &T) -> bool,
+{
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lt = &mut self.lt;
+        self.heap.pop(cmp!(lt>))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, F> ExactSizeIterator for LazySorted<'_, T, F>
+where F: FnMut(&T, &T) -> bool,
+{}
+
+impl<T, F> FusedIterator for LazySorted<'_, T, F>
+where F: FnMut(&T, &T) -> bool,
+{}
+
+/// Lazy sorting iterator
+///
+/// > 惰性排序, 按顺序产生元素, 但只做必要的工作.
+/// > 先在`arr`上原地建立小顶堆, 复杂度`O(n)`, 之后每次弹出堆顶`O(log(n))`,
+/// > 所以取前`m`个元素的复杂度为`O(n + m*log(n))`, 适合只消费前几页的分页场景
+/// >
+/// > 弹出的元素被移至堆的末尾, 全部取出后`arr`为逆序
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::lazy_sorted;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// let mut pages = lazy_sorted(&mut arr, i32::lt);
+/// let first = pages.by_ref().take(3).map(|x| *x).collect::<Vec<_>>();
+/// assert_eq!(first, [0, 1, 2]);
+/// assert_eq!(pages.len(), 5);
+/// assert_eq!(pages.next(), Some(&mut 3));
+/// ```
+pub fn lazy_sorted<T, F>(arr: &mut [T], mut lt: F) -> LazySorted<'_, T, F>
+where F: FnMut(&T, &T) -> bool,
+{
+    let mut heap = BHeap(arr);
+    heap.make(cmp!(lt>));
+    LazySorted { heap, lt }
+}
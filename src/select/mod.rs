@@ -0,0 +1,28 @@
+//! 选择算法, 不完全排序而找到第`k`小的元素
+//!
+//! 其中的选择都将`arr`划分为: `arr[..k]`均不大于`arr[k]`, `arr[k+1..]`均不小于`arr[k]`,
+//! 即`arr[k]`与完全排序后的位置相同
+//!
+//! - 基于划分的选择, 如快速选择, 平均复杂度`O(n)`
+//!
+//! - 保证线性的选择, 如中位数的中位数
+//!
+//! - 只排序最前的`k`个元素, 如部分排序, 以及流式地收集前`k`个元素
+//!
+//! - 惰性排序, 按需逐个产生有序的元素
+
+mod quick;
+mod intro;
+mod floyd_rivest;
+mod nth;
+mod partial;
+mod top_k;
+mod lazy;
+
+pub use quick::*;
+pub use intro::*;
+pub use floyd_rivest::*;
+pub use nth::*;
+pub use partial::*;
+pub use top_k::*;
+pub use lazy::*;
@@ -0,0 +1,94 @@
+use super::{floyd_rivest_inner, introselect_inner, FLOYD_RIVEST_SAMPLE};
+
+/// Partition `arr` around its `k`-th smallest element
+///
+/// > 类似 C++ 的`std::nth_element`, 较大的数据使用 [`floyd_rivest`],
+/// > 否则使用 [`introselect`]. 二者在划分过的总长度超过`4n`时
+/// > 都会改用中位数的中位数, 所以最坏复杂度为`O(n)`
+///
+/// [`floyd_rivest`]: super::floyd_rivest
+/// [`introselect`]: super::introselect
+///
+/// # Panics
+/// `k >= arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::nth_element;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// let mid = arr.len() / 2;
+/// nth_element(&mut arr, mid, i32::lt);
+/// assert_eq!(arr[mid], 4);
+/// assert!(arr[..mid].iter().all(|&x| x <= 4));
+/// assert!(arr[mid+1..].iter().all(|&x| x >= 4));
+/// ```
+pub fn nth_element<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k < arr.len(), "index {k} out of range for length {}", arr.len());
+    if arr.len() > FLOYD_RIVEST_SAMPLE {
+        let mut budget = 4 * arr.len();
+        floyd_rivest_inner(arr, 0, arr.len() - 1, k, &mut budget, &mut lt)
+    } else {
+        introselect_inner(arr, k, &mut lt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select::*;
+
+    use super::floyd_rivest_inner;
+
+    /// 较长且容易使基准退化的输入, 与完全排序的结果对比
+    #[test]
+    fn select_large_test() {
+        let len = 5000;
+        let inputs = [
+            (0..len).collect::<Vec<_>>(),
+            (0..len).rev().collect(),
+            vec![7; len],
+            (0..len).map(|i| i % 3).collect(),
+            (0..len).map(|i| if i % 2 == 0 { i } else { len - i }).collect(),
+            (0..len).map(|i| i * 7919 % 10007).collect(),
+        ];
+        let selects: [fn(&mut [usize], usize); 4] = [
+            |arr, k| median_of_medians(arr, k, usize::lt),
+            |arr, k| introselect(arr, k, usize::lt),
+            |arr, k| floyd_rivest(arr, k, usize::lt),
+            |arr, k| nth_element(arr, k, usize::lt),
+        ];
+        for input in &inputs {
+            let mut sorted = input.clone();
+            sorted.sort();
+            for k in [0, 1, len / 3, len / 2, len - 2, len - 1] {
+                for select in selects {
+                    let mut arr = input.clone();
+                    select(&mut arr, k);
+                    assert_eq!(arr[k], sorted[k]);
+                    assert!(arr[..k].iter().all(|&x| x <= arr[k]));
+                    assert!(arr[k+1..].iter().all(|&x| x >= arr[k]));
+                }
+            }
+        }
+    }
+
+    /// 预算不足时 Floyd–Rivest 改用中位数的中位数, 结果仍然正确
+    #[test]
+    fn floyd_rivest_budget_test() {
+        let len = 5000;
+        let input = (0..len).map(|i| i * 7919 % 10007).collect::<Vec<_>>();
+        let mut sorted = input.clone();
+        sorted.sort();
+        for budget in [0, len / 2, len, 2 * len] {
+            for k in [0, len / 3, len - 1] {
+                let mut arr = input.clone();
+                let mut budget = budget;
+                floyd_rivest_inner(&mut arr, 0, len - 1, k, &mut budget, &mut usize::lt);
+                assert_eq!(arr[k], sorted[k]);
+                assert!(arr[..k].iter().all(|&x| x <= arr[k]));
+                assert!(arr[k+1..].iter().all(|&x| x >= arr[k]));
+            }
+        }
+    }
+}
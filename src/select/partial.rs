@@ -0,0 +1,72 @@
+use crate::{cmp, normal::BHeap};
+
+/// Partial sort
+///
+/// > 部分排序, 将最小的`k`个元素有序地放在`arr[..k]`, 其余元素的顺序不确定.
+/// > 在`arr[..k]`上建立大顶堆, 之后每个比堆顶小的元素替换堆顶,
+/// > 最后对堆进行堆排序, 复杂度`O(n*log(k))`
+///
+/// # Panics
+/// `k > arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::partial_sort;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// partial_sort(&mut arr, 3, i32::lt);
+/// assert_eq!(arr[..3], [0, 1, 2]);
+/// ```
+pub fn partial_sort<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k <= arr.len(), "k {k} out of range for length {}", arr.len());
+    if k == 0 { return }
+    let (head, rest) = arr.split_at_mut(k);
+    let mut bheap = BHeap(head);
+    bheap.make(&mut lt);
+    for ele in rest {
+        if cmp!(lt(*ele,< bheap.0[0])) {
+            std::mem::swap(ele, &mut bheap.0[0]);
+            bheap.filter_down(0, &mut lt).unwrap();
+        }
+    }
+    bheap.pop_to(0.., &mut lt);
+}
+
+/// Partial sort into `dest`, like [`partial_sort`]
+///
+/// > 将`src`中最小的`m = min(src.len(), dest.len())`个元素有序地复制到`dest[..m]`,
+/// > 不修改`src`, 只会克隆最终结果中的元素以及替换堆顶的元素
+///
+/// 返回`m`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::partial_sort_copy;
+/// let src = [0, 3, 1, 5, 2, 9, 6, 4];
+/// let mut dest = [0; 3];
+/// assert_eq!(partial_sort_copy(&src, &mut dest, i32::gt), 3);
+/// assert_eq!(dest, [9, 6, 5]);
+///
+/// let mut dest = [0; 10];
+/// assert_eq!(partial_sort_copy(&src[..2], &mut dest, i32::lt), 2);
+/// assert_eq!(dest[..2], [0, 3]);
+/// ```
+pub fn partial_sort_copy<T, F>(src: &[T], dest: &mut [T], mut lt: F) -> usize
+where F: FnMut(&T, &T) -> bool,
+      T: Clone,
+{
+    let m = src.len().min(dest.len());
+    if m == 0 { return 0 }
+    dest[..m].clone_from_slice(&src[..m]);
+    let mut bheap = BHeap(&mut dest[..m]);
+    bheap.make(&mut lt);
+    for ele in &src[m..] {
+        if cmp!(lt(*ele,< bheap.0[0])) {
+            bheap.0[0].clone_from(ele);
+            bheap.filter_down(0, &mut lt).unwrap();
+        }
+    }
+    bheap.pop_to(0.., &mut lt);
+    m
+}
@@ -0,0 +1,47 @@
+use std::{cmp::Ordering, mem};
+
+use crate::normal::partition;
+
+pub(super) fn quickselect_inner<T, F>(mut arr: &mut [T], mut k: usize, lt: &mut F)
+where F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        let p = partition(arr, lt);
+        let (low, high) = mem::take(&mut arr).split_at_mut(p);
+        match k.cmp(&p) {
+            Ordering::Equal => return,
+            Ordering::Less => arr = low,
+            Ordering::Greater => {
+                arr = &mut high[1..];
+                k -= p + 1;
+            },
+        }
+    }
+}
+
+/// Quickselect
+///
+/// > 快速选择, 使用与 [`quick_sort`] 相同的划分, 但只进入包含`k`的一侧,
+/// > 平均复杂度`O(n)`, 与快速排序一样以最后一个元素为基准,
+/// > 所以在已经有序或大量重复的数据上会退化至`O(n^2)`
+///
+/// [`quick_sort`]: crate::normal::quick_sort
+///
+/// # Panics
+/// `k >= arr.len()`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::quickselect;
+/// let mut arr = [0, 3, 1, 5, 2, 9, 6, 4];
+/// quickselect(&mut arr, 3, i32::lt);
+/// assert_eq!(arr[3], 3);
+/// assert!(arr[..3].iter().all(|&x| x <= 3));
+/// assert!(arr[4..].iter().all(|&x| x >= 3));
+/// ```
+pub fn quickselect<T, F>(arr: &mut [T], k: usize, mut lt: F)
+where F: FnMut(&T, &T) -> bool,
+{
+    assert!(k < arr.len(), "index {k} out of range for length {}", arr.len());
+    quickselect_inner(arr, k, &mut lt)
+}
@@ -0,0 +1,98 @@
+use crate::{cmp, normal::BHeap};
+
+/// Streaming top-k collector
+///
+/// > 从流中收集排序后最前的`k`个元素, 即在`lt`下最小的`k`个,
+/// > 使用`gt`时则为最大的`k`个.
+/// > 使用容量为`k`的大顶堆, 堆顶是当前保留的元素中最大的,
+/// > 新元素比堆顶小时才替换堆顶, 每个元素`O(log(k))`, 只需`O(k)`的空间
+/// >
+/// > 与堆顶相等的新元素不会被保留, 即边界上相等时保留先出现的
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::TopK;
+/// let mut top = TopK::new(3, i32::gt);
+/// top.extend([0, 3, 1, 5, 2, 9, 6, 4]);
+/// assert_eq!(top.peek(), Some(&5));
+/// top.push(7);
+/// assert_eq!(top.into_sorted_vec(), [9, 7, 6]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TopK<T, F> {
+    k: usize,
+    heap: Vec<T>,
+    lt: F,
+}
+
+impl<T, F> TopK<T, F>
+where F: FnMut(&T, &T) -> bool,
+{
+    pub fn new(k: usize, lt: F) -> Self {
+        Self { k, heap: Vec::with_capacity(k), lt }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// 当前保留的元素数量, 不超过`k`
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// 当前保留的元素中最大的, 即排序后的最后一个
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.first()
+    }
+
+    pub fn push(&mut self, ele: T) {
+        let lt = &mut self.lt;
+        if self.heap.len() < self.k {
+            self.heap.push(ele);
+            let last = self.heap.len() - 1;
+            BHeap(&mut self.heap).filter_up(last, lt);
+        } else if self.k > 0 && cmp!(lt(ele,< self.heap[0])) {
+            self.heap[0] = ele;
+            BHeap(&mut self.heap).filter_down(0, lt).unwrap();
+        }
+    }
+
+    /// 按`lt`排好序的保留元素
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut bheap = BHeap(&mut self.heap[..]);
+        bheap.pop_to(0.., &mut self.lt);
+        self.heap
+    }
+}
+
+impl<T, F> Extend<T> for TopK<T, F>
+where F: FnMut(&T, &T) -> bool,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for ele in iter {
+            self.push(ele);
+        }
+    }
+}
+
+/// Collect the first `k` elements of sorted `iter`, see [`TopK`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::select::top_k;
+/// let top = top_k(0..1_000_000, 3, u32::gt);
+/// assert_eq!(top, [999_999, 999_998, 999_997]);
+/// ```
+pub fn top_k<T, F, I>(iter: I, k: usize, lt: F) -> Vec<T>
+where F: FnMut(&T, &T) -> bool,
+      I: IntoIterator<Item = T>,
+{
+    let mut top = TopK::new(k, lt);
+    top.extend(iter);
+    top.into_sorted_vec()
+}
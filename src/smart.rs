@@ -0,0 +1,392 @@
+//! 自适应的排序, 根据输入的特征选择已有的排序算法
+//!
+//! > 检查长度, 完整扫描一次下降的位置, 并均匀采样计算 [`metrics`] 中的有序程度,
+//! > 对实现了 [`RadixKey`] 的类型还会检查键的范围与位数, 之后选择:
+//! >
+//! > - 很短或基本有序时, 使用 [`insert_sort`]. 下降的位置与样本都可能看起来有序,
+//! >   而逆序对依然有`O(n^2)`个, 所以还要求整个输入的逆序对数量不超过`n`的 [`SMART_INVERSIONS_PER_ELEMENT`] 倍
+//! > - 键的范围较小时, 使用 [`count_sort`]
+//! > - 键的位数较少时, 使用 [`radix_sort`]
+//! > - 要求稳定, 或者数据有一定结构以及大量重复时, 使用 [`merge_sort`]
+//! > - 下降的位置数量接近随机数据的`n/2`, 且样本看起来是随机且没有重复时, 使用 [`quick_sort`].
+//! >   [`quick_sort`] 在有序的段上会退化到`O(n^2)`, 所以只看样本是不够的
+//! >
+//! > 选择的结果与原因都会作为 [`Decision`] 返回, 可以直接打印
+//!
+//! [`metrics`]: crate::metrics
+//! [`insert_sort`]: crate::basic::insert_sort
+//! [`count_sort`]: crate::other::count_sort
+//! [`radix_sort`]: crate::other::radix_sort
+//! [`merge_sort`]: crate::normal::merge_sort
+//! [`quick_sort`]: crate::normal::quick_sort
+
+use std::fmt;
+
+use crate::{
+    basic::insert_sort,
+    metrics::{self, Metrics},
+    normal::{merge_sort, quick_sort},
+    other::{count_sort, radix_sort, RadixKey, RadixUint},
+    utils::IterMinMax,
+};
+
+/// 不超过这个长度时直接插入排序
+pub const SMART_SMALL_LEN: usize = 32;
+
+/// 采样的元素数量
+pub const SMART_SAMPLE_LEN: usize = 64;
+
+/// 基本有序时, 平均每个元素允许的逆序对数量, 插入排序的复杂度为`O(n + inversions)`
+pub const SMART_INVERSIONS_PER_ELEMENT: usize = 8;
+
+/// The algorithm chosen by smart sorts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Choice {
+    InsertSort,
+    MergeSort,
+    QuickSort,
+    CountSort,
+    RadixSort,
+}
+
+impl Choice {
+    /// Path in this crate, same as [`registry`](crate::registry)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::InsertSort => "basic::insert_sort",
+            Self::MergeSort => "normal::merge_sort",
+            Self::QuickSort => "normal::quick_sort",
+            Self::CountSort => "other::count_sort",
+            Self::RadixSort => "other::radix_sort",
+        }
+    }
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Why the algorithm is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    /// 长度不超过 [`SMART_SMALL_LEN`]
+    Small,
+    /// 下降的位置很少, 样本基本有序, 且整个输入的逆序对较少
+    NearlySorted,
+    /// 最大键与最小键的差较小
+    SmallKeyRange { range: u128 },
+    /// 最大键的有效位数较少
+    FewKeyBits { bits: u32 },
+    /// 要求稳定排序
+    Stable,
+    /// 下降的位置数量与样本的逆序对比例都接近随机, 且没有重复
+    RandomLooking,
+    /// 其它情况, 归并排序没有退化的情况
+    Fallback,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Small => write!(f, "at most {SMART_SMALL_LEN} elements"),
+            Self::NearlySorted => write!(f, "nearly sorted"),
+            Self::SmallKeyRange { range } => write!(f, "small key range {range}"),
+            Self::FewKeyBits { bits } => write!(f, "keys fit in {bits} bits"),
+            Self::Stable => write!(f, "stable sort requested"),
+            Self::RandomLooking => write!(f, "looks random without duplicates"),
+            Self::Fallback => write!(f, "presorted or duplicated"),
+        }
+    }
+}
+
+/// Decision of smart sorts, and the evidence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub choice: Choice,
+    pub reason: Reason,
+    pub len: usize,
+    /// 满足`arr[i+1] < arr[i]`的位置数量, 较短时不计算
+    pub descents: Option<usize>,
+    /// 整个输入的逆序对数量, 只在看起来基本有序时计算
+    pub inversions: Option<usize>,
+    /// 样本的有序程度, 较短时不计算
+    pub sample: Option<Metrics>,
+    /// 样本中与前一个相等的元素数量
+    pub sample_duplicates: usize,
+}
+
+impl Decision {
+    fn new(choice: Choice, reason: Reason, len: usize) -> Self {
+        Self {
+            choice,
+            reason,
+            len,
+            descents: None,
+            inversions: None,
+            sample: None,
+            sample_duplicates: 0,
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} (n={}", self.choice, self.reason, self.len)?;
+        if let Some(descents) = self.descents {
+            write!(f, ", descents={descents}")?;
+        }
+        if let Some(inversions) = self.inversions {
+            write!(f, ", inversions={inversions}")?;
+        }
+        if let Some(sample) = self.sample {
+            write!(f, ", sample disorder={:.2}, sample rem={}, sample duplicates={}",
+                sample.disorder(), sample.rem, self.sample_duplicates)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// 只使用比较的决策
+fn decide<T, F>(arr: &[T], stable: bool, lt: &mut F) -> Decision
+where F: FnMut(&T, &T) -> bool,
+{
+    let len = arr.len();
+    if len <= SMART_SMALL_LEN {
+        return Decision::new(Choice::InsertSort, Reason::Small, len);
+    }
+
+    let descents = metrics::runs(arr, &mut *lt) - 1;
+    let sample = (0..SMART_SAMPLE_LEN)
+        .map(|k| &arr[k * len / SMART_SAMPLE_LEN])
+        .collect::<Vec<_>>();
+    let metrics = metrics::analyze(&sample, |a, b| lt(a, b));
+    let mut order = (0..sample.len()).collect::<Vec<_>>();
+    merge_sort(&mut order, |&a, &b| lt(sample[a], sample[b]));
+    let duplicates = order.windows(2)
+        .filter(|w| !lt(sample[w[0]], sample[w[1]]))
+        .count();
+
+    // 下降的位置与样本只是必要条件, 还需要完整计算逆序对才能保证插入排序不会退化
+    let inversions = (descents * 16 <= len && metrics.rem <= 2)
+        .then(|| metrics::inversions(arr, &mut *lt));
+
+    let (choice, reason) = if inversions.is_some_and(|inv| inv <= SMART_INVERSIONS_PER_ELEMENT * len) {
+        (Choice::InsertSort, Reason::NearlySorted)
+    } else if stable {
+        (Choice::MergeSort, Reason::Stable)
+    } else if descents.abs_diff(len / 2) <= len / 8
+        && duplicates == 0
+        && (0.25..=0.75).contains(&metrics.disorder())
+    {
+        (Choice::QuickSort, Reason::RandomLooking)
+    } else {
+        (Choice::MergeSort, Reason::Fallback)
+    };
+    Decision {
+        descents: Some(descents),
+        inversions,
+        sample: Some(metrics),
+        sample_duplicates: duplicates,
+        ..Decision::new(choice, reason, len)
+    }
+}
+
+fn apply<T, F>(arr: &mut [T], choice: Choice, lt: F)
+where F: FnMut(&T, &T) -> bool,
+      T: Default,
+{
+    match choice {
+        Choice::InsertSort => insert_sort(arr, lt),
+        Choice::MergeSort => merge_sort(arr, lt),
+        Choice::QuickSort => quick_sort(arr, lt),
+        Choice::CountSort | Choice::RadixSort => unreachable!("{choice} needs RadixKey"),
+    }
+}
+
+/// Smart sort, choose an algorithm by input characteristics
+///
+/// > 见 [模块文档](self), 不要求稳定, 只会选择比较排序
+///
+/// # Example
+/// ```
+/// # use sorts_rs::smart::{smart_sort, Choice, Reason};
+/// let mut arr = (0..1000).collect::<Vec<_>>();
+/// arr.swap(10, 11);
+/// let decision = smart_sort(&mut arr, i32::lt);
+/// assert_eq!(arr, (0..1000).collect::<Vec<_>>());
+/// assert_eq!((decision.choice, decision.reason), (Choice::InsertSort, Reason::NearlySorted));
+/// println!("{decision}");
+/// ```
+pub fn smart_sort<T, F>(arr: &mut [T], mut lt: F) -> Decision
+where F: FnMut(&T, &T) -> bool,
+      T: Default,
+{
+    let decision = decide(arr, false, &mut lt);
+    apply(arr, decision.choice, lt);
+    decision
+}
+
+/// Smart sort, like [`smart_sort`], but only choose stable sorts
+///
+/// **is stable sort**
+///
+/// # Example
+/// ```
+/// # use sorts_rs::smart::{smart_sort_stable, Choice};
+/// let mut arr = (0..1000).map(|i| (i * 7919 % 1009, i)).collect::<Vec<_>>();
+/// let decision = smart_sort_stable(&mut arr, |a, b| a.0 / 10 < b.0 / 10);
+/// assert_eq!(decision.choice, Choice::MergeSort);
+/// assert!(arr.windows(2).all(|w| (w[0].0 / 10, w[0].1) < (w[1].0 / 10, w[1].1)));
+/// ```
+pub fn smart_sort_stable<T, F>(arr: &mut [T], mut lt: F) -> Decision
+where F: FnMut(&T, &T) -> bool,
+      T: Default,
+{
+    let decision = decide(arr, true, &mut lt);
+    apply(arr, decision.choice, lt);
+    decision
+}
+
+/// Smart sort by [`RadixKey`], may choose counting or radix sort
+///
+/// > 按键的顺序排序, 键与值一一对应, 所以相等的元素无法区分, 无需考虑稳定性.
+/// > 很短或基本有序时依然使用插入排序,
+/// > 否则键的范围小于`2n`时计数排序, 键的位数不超过`2*log2(n)`时基数排序,
+/// > 都不满足时与 [`smart_sort`] 相同
+///
+/// # Example
+/// ```
+/// # use sorts_rs::smart::{smart_sort_keys, Choice};
+/// let mut arr = (0..1000).map(|i| i * 7919 % 1009 - 500).collect::<Vec<i32>>();
+/// let decision = smart_sort_keys(&mut arr);
+/// assert_eq!(decision.choice, Choice::CountSort);
+/// assert!(arr.is_sorted());
+///
+/// let mut arr = (0..1000).map(|i| i * 7919 % 65521).collect::<Vec<u32>>();
+/// assert_eq!(smart_sort_keys(&mut arr).choice, Choice::RadixSort);
+/// assert!(arr.is_sorted());
+/// ```
+pub fn smart_sort_keys<T>(arr: &mut [T]) -> Decision
+where T: RadixKey + Default,
+{
+    let mut lt = |a: &T, b: &T| a.to_radix_key() < b.to_radix_key();
+    let decision = decide(arr, false, &mut lt);
+    if matches!(decision.reason, Reason::Small | Reason::NearlySorted) {
+        apply(arr, decision.choice, lt);
+        return decision;
+    }
+
+    let (min, max) = arr.iter()
+        .map(|ele| ele.to_radix_key())
+        .minmax()
+        .unwrap();
+    let range = max.to_u128() - min.to_u128();
+    let bits = u128::BITS - max.to_u128().leading_zeros();
+    let log = arr.len().ilog2();
+    let decision = if range < 2 * arr.len() as u128 {
+        Decision { choice: Choice::CountSort, reason: Reason::SmallKeyRange { range }, ..decision }
+    } else if bits <= 2 * log {
+        Decision { choice: Choice::RadixSort, reason: Reason::FewKeyBits { bits }, ..decision }
+    } else {
+        decision
+    };
+    match decision.choice {
+        Choice::CountSort => count_sort(arr),
+        Choice::RadixSort => radix_sort(arr),
+        choice => apply(arr, choice, lt),
+    }
+    decision
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::datagen::Distribution;
+
+    fn decisions(dist: Distribution, len: usize) -> [Decision; 3] {
+        let input = dist.generate(len, &mut StdRng::seed_from_u64(0));
+        let mut sorted = input.clone();
+        sorted.sort();
+        let mut arrs = [input.clone(), input.clone(), input];
+        let decisions = [
+            smart_sort(&mut arrs[0], u64::lt),
+            smart_sort_stable(&mut arrs[1], u64::lt),
+            smart_sort_keys(&mut arrs[2]),
+        ];
+        for (arr, decision) in arrs.iter().zip(&decisions) {
+            assert_eq!(*arr, sorted, "{dist} {decision}");
+        }
+        decisions
+    }
+
+    #[test]
+    fn smart_sort_test() {
+        for dist in Distribution::ALL {
+            for len in [0, 1, 10, 33, 100, 1000, 5000] {
+                decisions(dist, len);
+            }
+        }
+
+        let choices = |dist| decisions(dist, 5000).map(|d| (d.choice, d.reason));
+        use {Choice::*, Reason::*};
+        assert_eq!(choices(Distribution::Sorted), [(InsertSort, NearlySorted); 3]);
+        let [unstable, stable, _] = choices(Distribution::Random);
+        assert_eq!(unstable, (QuickSort, RandomLooking));
+        assert_eq!(stable, (MergeSort, Stable));
+        let [unstable, _, keys] = choices(Distribution::Reversed);
+        assert_eq!(unstable, (MergeSort, Fallback));
+        assert_eq!(keys.0, CountSort);
+        let [unstable, _, _] = choices(Distribution::FewUnique);
+        assert_eq!(unstable, (MergeSort, Fallback));
+        assert_eq!(decisions(Distribution::Random, 10)[0].reason, Small);
+    }
+
+    /// 只有采样的位置被打乱的有序输入不是随机的, 不能选择会退化的快速排序
+    #[test]
+    fn smart_sort_scrambled_sample_test() {
+        use rand::seq::SliceRandom;
+
+        let len = 100_000;
+        let mut arr = (0..len).collect::<Vec<_>>();
+        let positions = (0..SMART_SAMPLE_LEN)
+            .map(|k| k * len / SMART_SAMPLE_LEN)
+            .collect::<Vec<_>>();
+        let mut values = positions.clone();
+        values.shuffle(&mut StdRng::seed_from_u64(0));
+        for (&p, &v) in positions.iter().zip(&values) {
+            arr[p] = v;
+        }
+        let mut sorted = arr.clone();
+        sorted.sort();
+
+        let decision = smart_sort(&mut arr, usize::lt);
+        assert_eq!((decision.choice, decision.reason), (Choice::MergeSort, Reason::Fallback), "{decision}");
+        assert_eq!(arr, sorted);
+    }
+
+    /// 下降的位置与样本都有序, 但是有一块较大的元素被移到开头, 逆序对约为`n^2 / 128`
+    #[test]
+    fn smart_sort_moved_block_test() {
+        let len = 100_000;
+        let block = len / 128;
+        let mut arr = (0..len).collect::<Vec<_>>();
+        arr[1..].rotate_right(block);
+        let mut sorted = arr.clone();
+        sorted.sort();
+
+        let mut output = arr.clone();
+        let decision = smart_sort(&mut output, usize::lt);
+        assert_eq!((decision.choice, decision.reason), (Choice::MergeSort, Reason::Fallback), "{decision}");
+        assert_eq!(decision.inversions, Some(block * (len - block - 1)));
+        assert_eq!(output, sorted);
+
+        let mut output = arr.clone();
+        let decision = smart_sort_keys(&mut output);
+        assert_ne!(decision.choice, Choice::InsertSort, "{decision}");
+        assert_eq!(output, sorted);
+    }
+}
@@ -0,0 +1,194 @@
+//! Panic safety and misbehaving comparator tests
+//!
+//! > 比较函数可能会 panic, 也可能不是一个全序关系 (如带有 NaN 的`f64::lt`),
+//! > 这些情况下排序结果无意义, 但是排序应当能够终止, 不越界,
+//! > 且结束或 panic 后数组依然是输入的一个排列, 即元素不丢失也不重复
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::*;
+
+use super::SortFn;
+
+const MAX_LEN: usize = 64;
+
+/// Inputs with duplicates, all values `> 0`, so taken `Default` value will be found
+fn inputs(seed: u64) -> impl Iterator<Item = Vec<usize>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..=MAX_LEN).map(move |len| {
+        let max = (len >> 1) + 2;
+        (0..len).map(|_| rng.gen_range(1..max)).collect()
+    })
+}
+
+fn counts<T: Hash + Eq>(arr: &[T]) -> HashMap<&T, usize> {
+    let mut map = HashMap::new();
+    for ele in arr {
+        *map.entry(ele).or_default() += 1;
+    }
+    map
+}
+
+fn assert_permutation<T>(name: &str, input: &[T], output: &[T])
+where T: Hash + Eq + Debug,
+{
+    assert!(counts(input) == counts(output),
+        "{name}: not a permutation of input\n\
+        input: {input:?}\n\
+        output: {output:?}");
+}
+
+fn all_sorts() -> Vec<(&'static str, SortFn<usize>)> {
+    let mut sorts = super::lt_sorts();
+    sorts.push(("other::bitonic_sort", |arr, lt| {
+        let len = arr.len().checked_next_power_of_two().unwrap_or_default() >> 1;
+        other::bitonic_sort(&mut arr[..len], lt)
+    }));
+    sorts
+}
+
+fn count_compares(sort: SortFn<usize>, input: &[usize]) -> usize {
+    let mut count = 0;
+    sort(&mut input.to_vec(), &mut |a, b| {
+        count += 1;
+        a < b
+    });
+    count
+}
+
+#[test]
+fn compare_panic_test() {
+    for (name, sort) in all_sorts() {
+        for input in inputs(0x5eed) {
+            let total = count_compares(sort, &input);
+            let step = (total >> 5).max(1);
+            for k in (0..total).step_by(step) {
+                let mut output = input.clone();
+                let mut count = 0;
+                let res = catch_unwind(AssertUnwindSafe(|| {
+                    sort(&mut output, &mut |a, b| {
+                        if count == k { panic!("injected panic") }
+                        count += 1;
+                        a < b
+                    })
+                }));
+                assert!(res.is_err(), "{name}: expect panic at compare {k}");
+                assert_permutation(name, &input, &output);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static FUSE: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Convert into radix key will panic after [`FUSE`] times
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+struct Bomb(usize);
+impl other::RadixKey for Bomb {
+    type Key = usize;
+
+    fn to_radix_key(self) -> Self::Key {
+        let fuse = FUSE.get();
+        assert_ne!(fuse, 0, "bomb");
+        FUSE.set(fuse - 1);
+        self.0
+    }
+
+    fn from_radix_key(key: Self::Key) -> Self {
+        Bomb(key)
+    }
+}
+
+fn check_convert_panic(name: &str, sort: fn(&mut [Bomb])) {
+    for input in inputs(0xbad) {
+        let input = input.into_iter().map(Bomb).collect::<Vec<_>>();
+        for k in 0..input.len() * 8 {
+            let mut output = input.clone();
+            FUSE.set(k);
+            let res = catch_unwind(AssertUnwindSafe(|| {
+                sort(&mut output)
+            }));
+            FUSE.set(usize::MAX);
+            if res.is_err() {
+                assert_permutation(name, &input, &output);
+            }
+        }
+    }
+}
+
+#[test]
+fn radix_sort_panic_test() {
+    check_convert_panic("other::radix_sort", other::radix_sort);
+    check_convert_panic("other::radix_sort_radix::<8>", other::radix_sort_radix::<8, _>);
+    check_convert_panic("other::msd_radix_sort", other::msd_radix_sort);
+    check_convert_panic("other::american_flag_sort", other::american_flag_sort);
+}
+
+/// Panic if compare too many times, that sort maybe never terminate
+fn budget<'a, F>(
+    name: &'a str,
+    len: usize,
+    mut lt: F,
+) -> impl FnMut(&usize, &usize) -> bool + 'a
+where F: FnMut(&usize, &usize) -> bool + 'a,
+{
+    let budget = 2 * len * len + 16;
+    let mut count = 0;
+    move |a, b| {
+        count += 1;
+        assert!(count <= budget, "{name}: compare more than {budget} times");
+        lt(a, b)
+    }
+}
+
+fn check_misbehave<F>(desc: &str, mut make_lt: impl FnMut() -> F)
+where F: FnMut(&usize, &usize) -> bool,
+{
+    for (name, sort) in all_sorts() {
+        let name = &*format!("{name} ({desc})");
+        for input in inputs(0xcafe) {
+            let mut output = input.clone();
+            let lt = budget(name, input.len(), make_lt());
+            sort(&mut output, &mut { lt });
+            assert_permutation(name, &input, &output);
+        }
+    }
+}
+
+#[test]
+fn random_compare_test() {
+    let mut rng = StdRng::seed_from_u64(0xd1ce);
+    check_misbehave("random", || {
+        let mut rng = StdRng::seed_from_u64(rng.gen());
+        move |_, _| rng.gen()
+    });
+}
+
+#[test]
+fn nan_compare_test() {
+    let map = |&n: &usize| if n % 3 == 0 { f64::NAN } else { n as f64 };
+    check_misbehave("f64 NaN", || {
+        move |a, b| map(a) < map(b)
+    });
+}
+
+#[test]
+fn always_true_compare_test() {
+    check_misbehave("always true", || |_, _| true);
+}
+
+#[test]
+fn non_transitive_compare_test() {
+    check_misbehave("rock paper scissors", || {
+        |a, b| (a + 1) % 3 == b % 3
+    });
+}
@@ -1 +1,14 @@
 mod random_case;
+mod prop;
+mod misbehave;
+
+use crate::*;
+
+use registry::LtSortFn as SortFn;
+
+/// All sorts using `lt` compare, and its name
+fn lt_sorts<T: Default>() -> Vec<(&'static str, SortFn<T>)> {
+    registry::lt_sorts().into_iter()
+        .map(|algo| (algo.name, algo.sort))
+        .collect()
+}
@@ -0,0 +1,558 @@
+//! Property based tests, use [`slice::sort_by`] as oracle
+//!
+//! > 随机生成输入并检查属性, 失败时会将输入收缩至最小用例, 并打印用于复现的种子
+//!
+//! - `SORTS_PROP_SEED`: 指定种子
+//! - `SORTS_PROP_CASES`: 每个属性检查的用例数量
+
+use rand::{rngs::StdRng, random, Rng, SeedableRng};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    env,
+    fmt::Debug,
+    hash::Hash,
+    panic::{catch_unwind, RefUnwindSafe},
+};
+
+use crate::*;
+
+const MAX_LEN: usize = 200;
+
+fn env_or<T: std::str::FromStr>(name: &str, default: impl FnOnce() -> T) -> T {
+    env::var(name).ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(default)
+}
+
+/// Generate an input, values often have duplicates
+fn gen_input(rng: &mut impl Rng) -> Vec<usize> {
+    let len = if rng.gen_ratio(1, 4) {
+        rng.gen_range(0..8)
+    } else {
+        rng.gen_range(0..=MAX_LEN)
+    };
+    let max_val = match rng.gen_range(0..4) {
+        0 => 2,
+        1 => len.max(1),
+        2 => len.max(1) * 4,
+        _ => usize::MAX >> 1,
+    };
+    (0..len).map(|_| rng.gen_range(0..max_val)).collect()
+}
+
+/// Shrinking candidates, each is simpler than `input`
+///
+/// remove chunks first, then shrink each value towards zero
+fn shrink_candidates(input: &[usize]) -> impl Iterator<Item = Vec<usize>> + '_ {
+    let len = input.len();
+    let removes = (0..usize::BITS)
+        .map(move |i| len >> i)
+        .take_while(|&size| size > 0)
+        .flat_map(move |size| {
+            (0..=len-size).step_by(size).map(move |i| {
+                let mut cand = input.to_vec();
+                cand.drain(i..i+size);
+                cand
+            })
+        });
+    let values = (0..len)
+        .filter(move |&i| input[i] != 0)
+        .flat_map(move |i| {
+            let n = input[i];
+            [0, n >> 1, n - 1].into_iter().map(move |ele| {
+                let mut cand = input.to_vec();
+                cand[i] = ele;
+                cand
+            })
+        });
+    removes.chain(values)
+}
+
+fn run_prop<P>(input: &[usize], prop: &P) -> Result<(), String>
+where P: Fn(&[usize]) -> Result<(), String> + RefUnwindSafe,
+{
+    catch_unwind(|| prop(input))
+        .unwrap_or_else(|e| {
+            let msg = e.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Err(format!("panicked: {msg}"))
+        })
+}
+
+/// Shrink a failed input, until no candidate fails
+fn minimize<P>(
+    mut input: Vec<usize>,
+    mut err: String,
+    prop: &P,
+) -> (Vec<usize>, String)
+where P: Fn(&[usize]) -> Result<(), String> + RefUnwindSafe,
+{
+    loop {
+        let failed = shrink_candidates(&input)
+            .find_map(|cand| run_prop(&cand, prop).err().map(|e| (cand, e)));
+        match failed {
+            Some(failed) => (input, err) = failed,
+            None => break (input, err),
+        }
+    }
+}
+
+/// Check a property, panic with minimal input and seed if failed
+fn check<P>(name: &str, prop: P)
+where P: Fn(&[usize]) -> Result<(), String> + RefUnwindSafe,
+{
+    let seed = env_or("SORTS_PROP_SEED", random::<u64>);
+    let cases = env_or("SORTS_PROP_CASES", || 128);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for _ in 0..cases {
+        let input = gen_input(&mut rng);
+        if let Err(err) = run_prop(&input, &prop) {
+            let (input, err) = minimize(input, err, &prop);
+            panic!("property {name} failed (SORTS_PROP_SEED={seed})\n\
+                minimal input: {input:?}\n\
+                error: {err}");
+        }
+    }
+}
+
+fn ordering_by<T>(lt: fn(&T, &T) -> bool) -> impl Fn(&T, &T) -> Ordering {
+    move |a, b| {
+        if lt(a, b) { Ordering::Less }
+        else if lt(b, a) { Ordering::Greater }
+        else { Ordering::Equal }
+    }
+}
+
+fn counts<T: Hash + Eq>(arr: &[T]) -> HashMap<&T, usize> {
+    let mut map = HashMap::new();
+    for ele in arr {
+        *map.entry(ele).or_default() += 1;
+    }
+    map
+}
+
+/// Sorted, is permutation of input, and same as [`slice::sort_by`]
+fn assert_sorted<T>(
+    input: &[T],
+    output: &[T],
+    lt: fn(&T, &T) -> bool,
+) -> Result<(), String>
+where T: Ord + Hash + Clone + Debug,
+{
+    if let Some(i) = output.windows(2).position(|w| lt(&w[1], &w[0])) {
+        return Err(format!("not sorted at {i}: {output:?}"));
+    }
+    if counts(input) != counts(output) {
+        return Err(format!("not a permutation of input: {output:?}"));
+    }
+    let mut expected = input.to_vec();
+    expected.sort_by(ordering_by(lt));
+    if expected != output {
+        return Err(format!("expected {expected:?}, found {output:?}"));
+    }
+    Ok(())
+}
+
+#[test]
+fn lt_sorts_prop() {
+    for (name, sort) in super::lt_sorts::<usize>() {
+        for lt in [usize::lt, usize::gt] {
+            check(name, |input| {
+                let mut output = input.to_vec();
+                sort(&mut output, &mut { lt });
+                assert_sorted(input, &output, lt)
+            });
+        }
+    }
+}
+
+#[test]
+fn count_sort_prop() {
+    check("other::count_sort", |input| {
+        let input = input.iter().map(|n| n % 1024).collect::<Vec<_>>();
+        let mut output = input.clone();
+        other::count_sort(&mut output);
+        assert_sorted(&input, &output, usize::lt)
+    });
+}
+
+#[test]
+fn radix_sort_prop() {
+    check("other::radix_sort", |input| {
+        let mut output = input.to_vec();
+        other::radix_sort(&mut output);
+        assert_sorted(input, &output, usize::lt)
+    });
+}
+
+/// Odd values to negative, even values stay positive
+fn signed(n: usize) -> i64 {
+    if n & 1 == 1 { -(n as i64) } else { n as i64 }
+}
+
+#[test]
+fn signed_radix_sorts_prop() {
+    check("other::count_sort (i64)", |input| {
+        let input = input.iter().map(|n| signed(n % 1024)).collect::<Vec<_>>();
+        let mut output = input.clone();
+        other::count_sort(&mut output);
+        assert_sorted(&input, &output, i64::lt)
+    });
+    check("other::radix_sort (i64)", |input| {
+        let input = input.iter().copied().map(signed).collect::<Vec<_>>();
+        let mut output = input.clone();
+        other::radix_sort(&mut output);
+        assert_sorted(&input, &output, i64::lt)
+    });
+    check("other::radix_sort_radix::<8> (i64)", |input| {
+        let input = input.iter().copied().map(signed).collect::<Vec<_>>();
+        let mut output = input.clone();
+        other::radix_sort_radix::<8, _>(&mut output);
+        assert_sorted(&input, &output, i64::lt)
+    });
+}
+
+#[test]
+fn radix_sort_radix_prop() {
+    fn check_bits<const BITS: u32>() {
+        let name = &*format!("other::radix_sort_radix::<{BITS}>");
+        check(name, |input| {
+            // 覆盖较大的值, 使得趟数更多
+            let input = input.iter()
+                .map(|&n| n.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+                .collect::<Vec<_>>();
+            let mut output = input.clone();
+            other::radix_sort_radix::<BITS, _>(&mut output);
+            let mut expected = input.clone();
+            other::radix_sort(&mut expected);
+            if output != expected {
+                return Err(format!("differ from other::radix_sort: {expected:?}"));
+            }
+            assert_sorted(&input, &output, usize::lt)
+        });
+    }
+    check_bits::<1>();
+    check_bits::<4>();
+    check_bits::<8>();
+    check_bits::<11>();
+    check_bits::<16>();
+}
+
+/// Byte strings with long common prefix, some are prefix of others
+fn byte_strings(input: &[usize]) -> Vec<Vec<u8>> {
+    input.iter()
+        .map(|&n| {
+            let prefix = if n & 1 == 1 { &[b'x'; 40][..] } else { b"" };
+            [prefix, &(n >> 1).to_string().into_bytes()].concat()
+        })
+        .collect()
+}
+
+fn check_msd<T>(name: &str, make: fn(&[usize]) -> Vec<T>, sort: fn(&mut [T]))
+where T: Ord + Hash + Clone + Debug,
+{
+    check(name, |input| {
+        let input = make(input);
+        let mut output = input.clone();
+        sort(&mut output);
+        assert_sorted(&input, &output, T::lt)
+    });
+}
+
+#[test]
+fn msd_radix_sorts_prop() {
+    let make = |input: &[usize]| input.iter().copied().map(signed).collect();
+    check_msd("other::msd_radix_sort", make, other::msd_radix_sort::<i64>);
+    check_msd("other::american_flag_sort", make, other::american_flag_sort::<i64>);
+    check_msd("other::msd_radix_sort_bytes", byte_strings, other::msd_radix_sort_bytes);
+    check_msd("other::american_flag_sort_bytes", byte_strings, other::american_flag_sort_bytes);
+}
+
+#[test]
+fn string_sorts_prop() {
+    check_msd("other::multikey_quicksort", byte_strings, other::multikey_quicksort);
+    check_msd("other::msd_string_sort", byte_strings, other::msd_string_sort);
+    check_msd("other::burstsort", byte_strings, other::burstsort);
+    check_msd("other::string_merge_sort", byte_strings, other::string_merge_sort);
+}
+
+/// Sort `(value, index)` records by `key(value)`, should be same as stable sort
+fn check_by_key<K: Ord>(
+    name: &str,
+    key: fn(usize) -> K,
+    sort: fn(&mut [(usize, usize)]),
+) {
+    check(name, |input| {
+        let mut output = input.iter().copied()
+            .zip(0..)
+            .collect::<Vec<_>>();
+        let mut expected = output.clone();
+        expected.sort_by_key(|&(n, _)| key(n));
+        sort(&mut output);
+        if output != expected {
+            return Err(format!("not stable sorted: {output:?}"));
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn by_key_sorts_prop() {
+    fn bucket(n: usize) -> usize { n % 1024 }
+
+    check_by_key("other::radix_sort_by_key", |n| n, |arr| {
+        other::radix_sort_by_key(arr, |&(n, _)| n)
+    });
+    check_by_key("other::radix_sort_by_key (i64)", signed, |arr| {
+        other::radix_sort_by_key(arr, |&(n, _)| signed(n))
+    });
+    check_by_key("other::counting_sort_by_key", bucket, |arr| {
+        other::counting_sort_by_key(arr, 0..1024, |&(n, _)| bucket(n))
+    });
+}
+
+/// `output[k]` same as sorted, and partitioned around it
+fn assert_selected(input: &[usize], output: &[usize], k: usize) -> Result<(), String> {
+    if counts(input) != counts(output) {
+        return Err(format!("not a permutation of input: {output:?}"));
+    }
+    let mut sorted = input.to_vec();
+    sorted.sort();
+    let nth = output[k];
+    if nth != sorted[k] {
+        return Err(format!("expected {} at {k}, found {nth}: {output:?}", sorted[k]));
+    }
+    if output[..k].iter().any(|&x| x > nth) || output[k+1..].iter().any(|&x| x < nth) {
+        return Err(format!("not partitioned at {k}: {output:?}"));
+    }
+    Ok(())
+}
+
+fn check_select<S>(name: &str, select: S)
+where S: Fn(&mut [usize], usize) + RefUnwindSafe,
+{
+    check(name, |input| {
+        let Some(&first) = input.first() else { return Ok(()) };
+        let len = input.len();
+        for k in [0, len / 2, len - 1, first % len] {
+            let mut output = input.to_vec();
+            select(&mut output, k);
+            assert_selected(input, &output, k)?;
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn select_prop() {
+    check_select("select::quickselect", |arr, k| select::quickselect(arr, k, usize::lt));
+    check_select("select::median_of_medians", |arr, k| {
+        select::median_of_medians(arr, k, usize::lt)
+    });
+    check_select("select::introselect", |arr, k| select::introselect(arr, k, usize::lt));
+    check_select("select::floyd_rivest", |arr, k| select::floyd_rivest(arr, k, usize::lt));
+    check_select("select::nth_element", |arr, k| select::nth_element(arr, k, usize::lt));
+}
+
+/// The first `k` of sorted input, by sorting a prefix of length `k`
+fn check_partial<S>(name: &str, partial: S)
+where S: Fn(&[usize], usize) -> Vec<usize> + RefUnwindSafe,
+{
+    check(name, |input| {
+        let mut sorted = input.to_vec();
+        sorted.sort();
+        let len = input.len();
+        for k in [0, 1, len / 2, len.saturating_sub(1), len, len + 1] {
+            let prefix = partial(input, k);
+            let expected = &sorted[..k.min(len)];
+            if prefix != expected {
+                return Err(format!("expected first {k} {expected:?}, found {prefix:?}"));
+            }
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn partial_sort_prop() {
+    check_partial("select::partial_sort", |input, k| {
+        let k = k.min(input.len());
+        let mut output = input.to_vec();
+        select::partial_sort(&mut output, k, usize::lt);
+        assert_eq!(counts(input), counts(&output), "not a permutation of input");
+        output.truncate(k);
+        output
+    });
+    check_partial("select::partial_sort_copy", |input, k| {
+        let mut dest = vec![0; k];
+        let m = select::partial_sort_copy(input, &mut dest, usize::lt);
+        dest.truncate(m);
+        dest
+    });
+    check_partial("select::TopK", |input, k| {
+        select::top_k(input.iter().copied(), k, usize::lt)
+    });
+    check_partial("select::lazy_sorted", |input, k| {
+        let mut arr = input.to_vec();
+        select::lazy_sorted(&mut arr, usize::lt).take(k).map(|x| *x).collect()
+    });
+}
+
+/// Compare metrics with brute force definitions
+#[test]
+fn metrics_prop() {
+    check("metrics::analyze", |input| {
+        let n = input.len();
+        let mut sorted = input.to_vec();
+        sorted.sort();
+        let pairs = || (0..n).flat_map(|i| (i+1..n).map(move |j| (i, j)));
+
+        // longest non-descending subsequence ending at each position
+        let mut lnds = vec![1; n];
+        for j in 0..n {
+            for i in 0..j {
+                if input[i] <= input[j] {
+                    lnds[j] = lnds[j].max(lnds[i] + 1);
+                }
+            }
+        }
+        let positions = {
+            let mut idx = (0..n).collect::<Vec<_>>();
+            idx.sort_by_key(|&i| input[i]);
+            idx
+        };
+        let expected = metrics::Metrics {
+            len: n,
+            is_sorted: *input == sorted,
+            inversions: pairs().filter(|&(i, j)| input[j] < input[i]).count(),
+            runs: if n == 0 { 0 } else { 1 + (1..n).filter(|&i| input[i] < input[i-1]).count() },
+            rem: n - lnds.iter().max().copied().unwrap_or(0),
+            osc: input.iter()
+                .map(|&x| input.windows(2)
+                    .filter(|w| w[0].min(w[1]) < x && x < w[0].max(w[1]))
+                    .count())
+                .sum(),
+            max_displacement: positions.iter()
+                .enumerate()
+                .map(|(k, &i)| k.abs_diff(i))
+                .max()
+                .unwrap_or(0),
+            ham: (0..n).filter(|&i| input[i] != sorted[i]).count(),
+        };
+        let found = metrics::analyze(input, usize::lt);
+        if found != expected {
+            return Err(format!("expected {expected:?}, found {found:?}"));
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn smart_sorts_prop() {
+    check("smart::smart_sort", |input| {
+        let mut output = input.to_vec();
+        smart::smart_sort(&mut output, usize::lt);
+        assert_sorted(input, &output, usize::lt)
+    });
+    check("smart::smart_sort_keys", |input| {
+        let input = input.iter().map(|&n| signed(n)).collect::<Vec<_>>();
+        let mut output = input.clone();
+        smart::smart_sort_keys(&mut output);
+        assert_sorted(&input, &output, i64::lt)
+    });
+    check_by_key("smart::smart_sort_stable", |n| n % 16, |arr| {
+        smart::smart_sort_stable(arr, |a, b| a.0 % 16 < b.0 % 16);
+    });
+}
+
+/// Small alphabet makes long repeats, the hard case for suffix arrays
+#[test]
+fn suffix_array_prop() {
+    check("other::suffix_array", |input| {
+        let text = input.iter().map(|&n| b'a' + (n % 3) as u8).collect::<Vec<_>>();
+        let expected = other::suffix_array_naive(&text);
+        for (name, sa) in [
+            ("other::suffix_array_doubling", other::suffix_array_doubling(&text)),
+            ("other::suffix_array", other::suffix_array(&text)),
+        ] {
+            if sa != expected {
+                return Err(format!("{name} wrong suffix array: {sa:?}"));
+            }
+        }
+        let (last, primary) = other::bwt(&text, &expected);
+        if other::inverse_bwt(&last, primary) != text {
+            return Err(format!("bwt not invertible: {last:?} {primary}"));
+        }
+        Ok(())
+    });
+}
+
+fn valid_bitonic_len(len: usize) -> bool {
+    len == 0 || len.is_power_of_two()
+}
+
+#[test]
+fn bitonic_sort_prop() {
+    check("other::bitonic_sort", |input| {
+        let mut output = input.to_vec();
+        let res = catch_unwind(move || {
+            other::bitonic_sort(&mut output, usize::lt);
+            output
+        });
+        match (res, valid_bitonic_len(input.len())) {
+            (Ok(output), true) => assert_sorted(input, &output, usize::lt),
+            (Err(_), false) => Ok(()),
+            (Ok(_), false) => Err("expect panic on invalid length".into()),
+            (Err(_), true) => Err("unexpected panic".into()),
+        }
+    });
+}
+
+#[test]
+fn bitonic_merge_prop() {
+    check("other::bitonic_merge", |input| {
+        let mut output = input.to_vec();
+        let mid = output.len() >> 1;
+        output[..mid].sort();
+        output[mid..].sort_by(|a, b| b.cmp(a));
+        let res = catch_unwind(move || {
+            other::bitonic_merge(&mut output, usize::lt);
+            output
+        });
+        match (res, valid_bitonic_len(input.len())) {
+            (Ok(output), true) => assert_sorted(input, &output, usize::lt),
+            (Err(_), false) => Ok(()),
+            (Ok(_), false) => Err("expect panic on invalid length".into()),
+            (Err(_), true) => Err("unexpected panic".into()),
+        }
+    });
+}
+
+#[test]
+fn shrink_to_minimal_test() {
+    let prop = |input: &[usize]| {
+        if input.len() >= 4 && input.iter().any(|&n| n >= 10) {
+            Err("found".to_string())
+        } else {
+            Ok(())
+        }
+    };
+    let input = vec![3, 28, 7, 100, 35, 6, 2, 9, 81];
+    let err = run_prop(&input, &prop).unwrap_err();
+    let (input, _) = minimize(input, err, &prop);
+    assert_eq!(input.len(), 4);
+    assert_eq!(input.iter().sum::<usize>(), 10);
+
+    let prop = |input: &[usize]| {
+        let mut output = input.to_vec();
+        output.sort_by(|a, b| b.cmp(a));
+        assert_sorted(input, &output, usize::lt)
+    };
+    let input = vec![5, 3, 9, 12, 0, 7];
+    let err = run_prop(&input, &prop).unwrap_err();
+    let (input, _) = minimize(input, err, &prop);
+    assert_eq!(input, [0, 1]);
+}
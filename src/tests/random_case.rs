@@ -22,33 +22,7 @@ fn shuffle<T>(arr: &mut [T]) {
 fn run_sorts<T>(arr: &[T])
 where T: Ord + Clone + Debug + Eq + Hash + Default,
 {
-    macro_rules! sorts {
-        ($($path:path),+ $(,)?) => {(
-            [
-                $(
-                    $path,
-                )+
-            ], [
-                $(
-                    stringify!($path),
-                )+
-            ]
-        )};
-    }
-    let (sorts, names) = sorts![
-        basic::bubble_sort,
-        basic::cocktail_sort,
-        basic::insert_sort,
-        basic::binary_insert_sort,
-        basic::select_sort,
-        basic::select_doubled_sort,
-        normal::comb_sort,
-        normal::shell_sort,
-        normal::sedgewick_sort,
-        normal::quick_sort,
-        normal::merge_sort,
-        normal::heap_sort,
-    ];
+    let (names, sorts): (Vec<_>, Vec<_>) = super::lt_sorts().into_iter().unzip();
     let mut table: HashMap<Vec<T>, Vec<_>> = HashMap::new();
     sorts.into_iter()
         .enumerate()
@@ -57,7 +31,7 @@ where T: Ord + Clone + Debug + Eq + Hash + Default,
         let mut sort_arr = arr.to_vec();
         let mut_slice = AssertUnwindSafe(sort_arr.as_mut_slice());
         if let Err(e) = catch_unwind(|| {
-            f({mut_slice}.0, T::lt);
+            f({mut_slice}.0, &mut T::lt);
         }) {
             eprintln!("{arr:?}");
             panic!("failed {} {e:?}", names[i]);
@@ -88,7 +62,7 @@ fn test_random_cases() {
         for buf in &mut bufs {
             buf.resize(len, 0);
             for ele in buf {
-                *ele = random::<usize>() % TEST_LEN >> 1;
+                *ele = (random::<usize>() % TEST_LEN) >> 1;
             }
         }
         thread::scope(|scope| {
@@ -112,7 +86,7 @@ fn bitonic_sort_test() {
         buf.resize(len, 0);
         buf1.resize(len, 0);
         for (a, b) in zip(&mut buf, &mut buf1) {
-            let num = random::<usize>() % len >> 1;
+            let num = (random::<usize>() % len) >> 1;
             *a = num;
             *b = num;
         }
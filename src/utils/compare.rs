@@ -0,0 +1,229 @@
+//! 组合比较函数, 得到的闭包可以直接用于所有排序的`lt`
+
+use std::cmp::Ordering;
+
+use super::natural_lt;
+
+/// `lt` from [`Ord`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::from_ord};
+/// let mut arr = ["b", "c", "a"];
+/// merge_sort(&mut arr, from_ord());
+/// assert_eq!(arr, ["a", "b", "c"]);
+/// ```
+pub fn from_ord<T: Ord + ?Sized>() -> impl FnMut(&T, &T) -> bool {
+    |a, b| a < b
+}
+
+/// `lt` from a function returning [`Ordering`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::from_ordering};
+/// let mut arr = [1.5, -0.0, 0.0, -1.0];
+/// merge_sort(&mut arr, from_ordering(f64::total_cmp));
+/// assert_eq!(arr, [-1.0, -0.0, 0.0, 1.5]);
+/// ```
+pub fn from_ordering<T, F>(mut cmp: F) -> impl FnMut(&T, &T) -> bool
+where F: FnMut(&T, &T) -> Ordering,
+      T: ?Sized,
+{
+    move |a, b| cmp(a, b).is_lt()
+}
+
+/// Compare by key
+///
+/// > 每次比较都会计算两次键, 键的代价较大时见 [`sort_by_cached_key`]
+///
+/// [`sort_by_cached_key`]: crate::permutation::sort_by_cached_key
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::by_key};
+/// let mut arr = ["ccc", "a", "bb"];
+/// merge_sort(&mut arr, by_key(|s: &&str| s.len()));
+/// assert_eq!(arr, ["a", "bb", "ccc"]);
+/// ```
+pub fn by_key<T, K, F>(mut f: F) -> impl FnMut(&T, &T) -> bool
+where F: FnMut(&T) -> K,
+      K: Ord,
+      T: ?Sized,
+{
+    move |a, b| f(a) < f(b)
+}
+
+/// Reverse order, like `cmp!(lt>)` but owns `lt`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::reverse};
+/// let mut arr = [0, 3, 1, 2];
+/// merge_sort(&mut arr, reverse(i32::lt));
+/// assert_eq!(arr, [3, 2, 1, 0]);
+/// ```
+pub fn reverse<T, F>(mut lt: F) -> impl FnMut(&T, &T) -> bool
+where F: FnMut(&T, &T) -> bool,
+      T: ?Sized,
+{
+    move |a, b| lt(b, a)
+}
+
+/// Compare by `lt1`, then by `lt2` when equal under `lt1`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::quick_sort, utils::{by_key, reverse, then}};
+/// let mut arr = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'a')];
+/// quick_sort(&mut arr, then(by_key(|p: &(i32, char)| p.0), reverse(|a: &(i32, char), b| a.1 < b.1)));
+/// assert_eq!(arr, [(0, 'b'), (0, 'a'), (1, 'c'), (1, 'a')]);
+/// ```
+pub fn then<T, F1, F2>(mut lt1: F1, mut lt2: F2) -> impl FnMut(&T, &T) -> bool
+where F1: FnMut(&T, &T) -> bool,
+      F2: FnMut(&T, &T) -> bool,
+      T: ?Sized,
+{
+    move |a, b| lt1(a, b) || !lt1(b, a) && lt2(a, b)
+}
+
+/// Compare by `lt`, then by key when equal under `lt`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::heap_sort, utils::{by_key, then_by_key}};
+/// let mut arr = ["bb", "a", "ab", "b"];
+/// heap_sort(&mut arr, then_by_key(by_key(|s: &&str| s.len()), |s: &&str| *s));
+/// assert_eq!(arr, ["a", "b", "ab", "bb"]);
+/// ```
+pub fn then_by_key<T, K, F, G>(lt: F, f: G) -> impl FnMut(&T, &T) -> bool
+where F: FnMut(&T, &T) -> bool,
+      G: FnMut(&T) -> K,
+      K: Ord,
+      T: ?Sized,
+{
+    then(lt, by_key(f))
+}
+
+/// `None` is less than any `Some`, and `Some` compared by `lt`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{basic::insert_sort, utils::nulls_first};
+/// let mut arr = [Some(2), None, Some(1), None];
+/// insert_sort(&mut arr, nulls_first(i32::lt));
+/// assert_eq!(arr, [None, None, Some(1), Some(2)]);
+/// ```
+pub fn nulls_first<T, F>(mut lt: F) -> impl FnMut(&Option<T>, &Option<T>) -> bool
+where F: FnMut(&T, &T) -> bool,
+{
+    move |a, b| match (a, b) {
+        (Some(a), Some(b)) => lt(a, b),
+        (a, b) => a.is_none() && b.is_some(),
+    }
+}
+
+/// `None` is greater than any `Some`, and `Some` compared by `lt`
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{basic::insert_sort, utils::nulls_last};
+/// let mut arr = [Some(2), None, Some(1), None];
+/// insert_sort(&mut arr, nulls_last(i32::lt));
+/// assert_eq!(arr, [Some(1), Some(2), None, None]);
+/// ```
+pub fn nulls_last<T, F>(mut lt: F) -> impl FnMut(&Option<T>, &Option<T>) -> bool
+where F: FnMut(&T, &T) -> bool,
+{
+    move |a, b| match (a, b) {
+        (Some(a), Some(b)) => lt(a, b),
+        (a, b) => a.is_some() && b.is_none(),
+    }
+}
+
+/// Total order of `f64`, same as [`f64::total_cmp`]
+///
+/// > `-NaN < -inf < .. < -0.0 < 0.0 < .. < inf < NaN`, 与 [`RadixKey`] 的顺序相同
+///
+/// [`RadixKey`]: crate::other::RadixKey
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::total_f64};
+/// let mut arr = [f64::NAN, 1.0, f64::NEG_INFINITY, -0.0, 0.0];
+/// merge_sort(&mut arr, total_f64);
+/// assert_eq!(arr[..4], [f64::NEG_INFINITY, -0.0, 0.0, 1.0]);
+/// assert!(arr[4].is_nan());
+/// ```
+pub fn total_f64(a: &f64, b: &f64) -> bool {
+    a.total_cmp(b).is_lt()
+}
+
+/// Natural string order, numbers compared by value, same as [`natural_lt`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::natural_str};
+/// let mut arr = ["img10.png", "img2.png", "img1.png", "img02.png"];
+/// merge_sort(&mut arr, natural_str);
+/// assert_eq!(arr, ["img1.png", "img2.png", "img02.png", "img10.png"]);
+/// ```
+pub fn natural_str<S>(a: &S, b: &S) -> bool
+where S: AsRef<str> + ?Sized,
+{
+    natural_lt(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::normal::merge_sort;
+
+    /// 长度与取值范围各异的输入, 大多包含重复
+    fn inputs() -> impl Iterator<Item = Vec<usize>> {
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..100).map(move |k| {
+            let len = k * 3;
+            let max = [2, len.max(1), usize::MAX >> 1][k % 3];
+            (0..len).map(|_| rng.gen_range(0..max)).collect()
+        })
+    }
+
+    /// 组合得到的比较函数与 [`Ordering`] 的组合一致
+    #[test]
+    fn then_test() {
+        for input in inputs() {
+            let mut output = input.clone();
+            merge_sort(&mut output, then_by_key(
+                reverse(by_key(|n: &usize| n % 7)),
+                |n: &usize| n / 7,
+            ));
+            let mut expected = input;
+            expected.sort_by(|a, b| (b % 7).cmp(&(a % 7)).then((a / 7).cmp(&(b / 7))));
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn nulls_test() {
+        for input in inputs() {
+            let input = input.iter()
+                .map(|&n| (n % 3 != 0).then_some(n))
+                .collect::<Vec<_>>();
+
+            let mut output = input.clone();
+            merge_sort(&mut output, nulls_last(from_ordering(usize::cmp)));
+            let mut expected = input.clone();
+            expected.sort_by_key(|n| (n.is_none(), *n));
+            assert_eq!(output, expected);
+
+            let mut output = input.clone();
+            merge_sort(&mut output, nulls_first(from_ord()));
+            let mut expected = input;
+            expected.sort();
+            assert_eq!(output, expected);
+        }
+    }
+}
@@ -1,11 +1,22 @@
 //! utils module
 
+mod compare;
+mod natural;
+
 use std::{cmp::Ordering, mem};
 
 pub use crate::{cmp, i};
+pub use compare::*;
+pub use natural::*;
 
 /// use `lessThan` function compare
 ///
+/// 只翻转单个`lt`, 组合比较函数见 [`by_key`], [`reverse`], [`then`] 等
+///
+/// [`by_key`]: crate::utils::by_key
+/// [`reverse`]: crate::utils::reverse
+/// [`then`]: crate::utils::then
+///
 /// # Example
 /// ```
 /// # use sorts_rs::cmp;
@@ -80,13 +91,18 @@ macro_rules! cmp {
 /// let mut b = 0;
 /// assert_eq!((i!(++a), i!(b++)), (1, 0));
 /// assert_eq!((a, b), (1, 1));
+///
+/// struct P { x: i32, y: i32 }
+/// let mut p = P { x: 0, y: 0 };
+/// assert_eq!((i!(p.x++), i!(--p.y)), (0, -1));
+/// assert_eq!((p.x, p.y), (1, -1));
 /// ```
 #[macro_export]
 macro_rules! i {
-    (++$i:ident) => {{ $i += 1; $i }};
-    (--$i:ident) => {{ $i -= 1; $i }};
-    ($i:ident++) => {{ let __tmp = $i; $i += 1; __tmp }};
-    ($i:ident--) => {{ let __tmp = $i; $i -= 1; __tmp }};
+    (++$($i:ident).+) => {{ $($i).+ += 1; $($i).+ }};
+    (--$($i:ident).+) => {{ $($i).+ -= 1; $($i).+ }};
+    ($($i:ident).+ ++) => {{ let __tmp = $($i).+; $($i).+ += 1; __tmp }};
+    ($($i:ident).+ --) => {{ let __tmp = $($i).+; $($i).+ -= 1; __tmp }};
 }
 
 pub trait IterMinMax: Iterator<Item: Clone> + Sized {
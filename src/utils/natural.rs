@@ -0,0 +1,288 @@
+//! 自然顺序 ("人类"顺序) 的字符串比较, 如`img2.png < img10.png`
+
+use std::cmp::Ordering;
+
+/// 各文字中十进制数字`0`的码位, 每个之后连续的 10 个字符依次为`0..=9`
+const DIGIT_ZEROS: [u32; 37] = [
+    0x0030, // ASCII
+    0x0660, // Arabic-Indic
+    0x06F0, // Extended Arabic-Indic
+    0x07C0, // NKo
+    0x0966, // Devanagari
+    0x09E6, // Bengali
+    0x0A66, // Gurmukhi
+    0x0AE6, // Gujarati
+    0x0B66, // Oriya
+    0x0BE6, // Tamil
+    0x0C66, // Telugu
+    0x0CE6, // Kannada
+    0x0D66, // Malayalam
+    0x0DE6, // Sinhala Lith
+    0x0E50, // Thai
+    0x0ED0, // Lao
+    0x0F20, // Tibetan
+    0x1040, // Myanmar
+    0x1090, // Myanmar Shan
+    0x17E0, // Khmer
+    0x1810, // Mongolian
+    0x1946, // Limbu
+    0x19D0, // New Tai Lue
+    0x1A80, // Tai Tham Hora
+    0x1A90, // Tai Tham Tham
+    0x1B50, // Balinese
+    0x1BB0, // Sundanese
+    0x1C40, // Lepcha
+    0x1C50, // Ol Chiki
+    0xA620, // Vai
+    0xA8D0, // Saurashtra
+    0xA900, // Kayah Li
+    0xA9D0, // Javanese
+    0xA9F0, // Myanmar Tai Laing
+    0xAA50, // Cham
+    0xABF0, // Meetei Mayek
+    0xFF10, // Fullwidth
+];
+
+/// 十进制数字的值, 支持 [`DIGIT_ZEROS`] 中的文字
+fn digit_value(c: char) -> Option<u32> {
+    if c.is_ascii() { return c.to_digit(10) }
+    if !c.is_numeric() { return None }
+    let c = u32::from(c);
+    DIGIT_ZEROS.iter()
+        .find(|&&zero| (zero..zero+10).contains(&c))
+        .map(|&zero| c - zero)
+}
+
+/// 从`s`的开头取出一段连续的数字或非数字, 返回是否为数字
+fn chunk(s: &str) -> (bool, &str, &str) {
+    let digits = s.starts_with(|c| digit_value(c).is_some());
+    let end = s.find(|c| digit_value(c).is_some() != digits).unwrap_or(s.len());
+    let (chunk, rest) = s.split_at(end);
+    (digits, chunk, rest)
+}
+
+/// 比较两段数字, 返回`(按数值比较, 按前导零数量比较)`
+fn cmp_digits(a: &str, b: &str) -> (Ordering, Ordering) {
+    let zeros = |s: &str| s.chars().take_while(|&c| digit_value(c) == Some(0)).count();
+    let (za, zb) = (zeros(a), zeros(b));
+    let (la, lb) = (a.chars().count() - za, b.chars().count() - zb);
+    fn digits(s: &str, zeros: usize) -> impl Iterator<Item = u32> + '_ {
+        s.chars().skip(zeros).filter_map(digit_value)
+    }
+    let value = la.cmp(&lb).then_with(|| digits(a, za).cmp(digits(b, zb)));
+    (value, za.cmp(&zb))
+}
+
+/// 比较两段非数字, 返回`(主要顺序, 次要顺序)`, 忽略大小写时大小写的不同是次要的
+fn cmp_text(a: &str, b: &str, fold_case: bool) -> (Ordering, Ordering) {
+    if !fold_case { return (a.cmp(b), Ordering::Equal) }
+    fn lower(s: &str) -> impl Iterator<Item = char> + '_ {
+        s.chars().flat_map(char::to_lowercase)
+    }
+    (lower(a).cmp(lower(b)), a.cmp(b))
+}
+
+/// Natural order of strings
+///
+/// > 将字符串分为连续的数字段与非数字段, 逐段比较:
+/// >
+/// > - 数字段按数值比较, 任意长度, 支持常见文字的十进制数字 (如`٣`, `३`, `３`)
+/// > - 数字段小于非数字段
+/// > - 非数字段按字符比较, `fold_case`时忽略大小写
+/// >
+/// > 所有段都相等时, 再依次以第一处前导零数量的不同 (较少的更小)、
+/// > 大小写的不同、原始字符串的顺序决定, 所以只有相同的字符串才相等
+///
+/// # Example
+/// ```
+/// # use sorts_rs::utils::natural_cmp;
+/// # use std::cmp::Ordering;
+/// assert_eq!(natural_cmp("img2.png", "img10.png", false), Ordering::Less);
+/// assert_eq!(natural_cmp("IMG2", "img10", false), Ordering::Less);
+/// assert_eq!(natural_cmp("img2", "IMG10", false), Ordering::Greater);
+/// assert_eq!(natural_cmp("img2", "IMG10", true), Ordering::Less);
+/// ```
+pub fn natural_cmp(a: &str, b: &str, fold_case: bool) -> Ordering {
+    let (mut ra, mut rb) = (a, b);
+    let mut tie = Ordering::Equal;
+    loop {
+        if ra.is_empty() || rb.is_empty() {
+            let len = ra.is_empty().cmp(&rb.is_empty()).reverse();
+            return len.then(tie).then_with(|| a.cmp(b));
+        }
+        let ((da, ca, rest_a), (db, cb, rest_b)) = (chunk(ra), chunk(rb));
+        let (primary, secondary) = match (da, db) {
+            (true, true) => cmp_digits(ca, cb),
+            (false, false) => cmp_text(ca, cb, fold_case),
+            (digits, _) => (if digits { Ordering::Less } else { Ordering::Greater }, Ordering::Equal),
+        };
+        if primary.is_ne() { return primary }
+        tie = tie.then(secondary);
+        (ra, rb) = (rest_a, rest_b);
+    }
+}
+
+/// Natural order `lt`, see [`natural_cmp`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{normal::merge_sort, utils::natural_lt};
+/// let mut arr = ["img10.png", "img02.png", "img2.png", "IMG3.png", "img1.png"];
+/// merge_sort(&mut arr, natural_lt);
+/// assert_eq!(arr, ["IMG3.png", "img1.png", "img2.png", "img02.png", "img10.png"]);
+/// ```
+pub fn natural_lt<S>(a: &S, b: &S) -> bool
+where S: AsRef<str> + ?Sized,
+{
+    natural_cmp(a.as_ref(), b.as_ref(), false).is_lt()
+}
+
+/// Natural order `lt` ignoring case, see [`natural_cmp`]
+///
+/// # Example
+/// ```
+/// # use sorts_rs::{basic::insert_sort, utils::natural_lt_fold_case};
+/// let mut arr = ["img10.png", "img2.png", "IMG3.png", "img1.png"];
+/// insert_sort(&mut arr, natural_lt_fold_case);
+/// assert_eq!(arr, ["img1.png", "img2.png", "IMG3.png", "img10.png"]);
+/// ```
+pub fn natural_lt_fold_case<S>(a: &S, b: &S) -> bool
+where S: AsRef<str> + ?Sized,
+{
+    natural_cmp(a.as_ref(), b.as_ref(), true).is_lt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{basic::insert_sort, normal::merge_sort, registry};
+
+    use Ordering::*;
+
+    #[test]
+    fn digit_zeros_test() {
+        for zero in DIGIT_ZEROS {
+            for d in 0..10 {
+                let c = char::from_u32(zero + d).unwrap();
+                assert_eq!(digit_value(c), Some(d), "{c:?}");
+            }
+        }
+        for c in ['a', '-', '½', '²', 'Ⅻ', '〇', ' '] {
+            assert_eq!(digit_value(c), None, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn natural_cmp_test() {
+        let table = [
+            // 基本
+            ("", "", false, Equal),
+            ("", "a", false, Less),
+            ("", "0", false, Less),
+            ("a", "a", false, Equal),
+            ("a", "b", false, Less),
+            ("a1", "a", false, Greater),
+            ("img2.png", "img10.png", false, Less),
+            ("img10.png", "img2.png", false, Greater),
+            ("img12.png", "img10.png", false, Greater),
+            ("x2-g8", "x2-y7", false, Less),
+            ("x2-y08", "x2-y7", false, Greater),
+            ("x8-y8", "x2-y8", false, Greater),
+            // 多段数字
+            ("1.2.10", "1.2.9", false, Greater),
+            ("1.10.1", "1.9.10", false, Greater),
+            ("v1.0.0", "v1.0", false, Greater),
+            ("2024-01-05", "2024-1-5", false, Greater),
+            ("2024-1-10", "2024-01-9", false, Greater),
+            ("a1b2c3", "a1b2c10", false, Less),
+            // 任意长度与前导零
+            ("123456789012345678901234567890", "123456789012345678901234567891", false, Less),
+            ("99999999999999999999999", "100000000000000000000000", false, Less),
+            ("file0001", "file1", false, Greater),
+            ("file01", "file001", false, Less),
+            ("file001", "file2", false, Less),
+            ("0", "00", false, Less),
+            ("00", "000", false, Less),
+            ("a01b", "a1c", false, Less),
+            ("a1b", "a01b", false, Less),
+            // 数字与非数字
+            ("1", "a", false, Less),
+            ("a", "1", false, Greater),
+            ("-1", "1", false, Greater),
+            ("file", "file1", false, Less),
+            ("file1", "file-1", false, Less),
+            ("file_1", "file-1", false, Greater),
+            ("file 2", "file 10", false, Less),
+            ("file.2", "file_10", false, Less),
+            ("file_2", "file.10", false, Greater),
+            ("a 2", "a  1", false, Less),
+            // 大小写
+            ("A", "a", false, Less),
+            ("B", "a", false, Less),
+            ("B", "a", true, Greater),
+            ("A", "a", true, Less),
+            ("abc", "ABC", true, Greater),
+            ("Img10", "img2", true, Greater),
+            ("IMG2", "img2", true, Less),
+            ("straße", "STRASSE", true, Greater),
+            ("Ä1", "ä2", true, Less),
+            ("ä1", "Ä2", false, Greater),
+            // Unicode 数字
+            ("٣", "3", false, Greater),
+            ("٣", "4", false, Less),
+            ("img٢", "img١٠", false, Less),
+            ("page३", "page12", false, Less),
+            ("ファイル２", "ファイル１０", false, Less),
+            ("第9章", "第10章", false, Less),
+            ("item๙", "item๑๐", false, Less),
+            ("x１", "x1", false, Greater),
+            ("x０1", "x1", false, Greater),
+            // 不是十进制数字的数字字符按非数字比较
+            ("a½", "a1", false, Greater),
+            ("x²", "x10", false, Greater),
+            ("Ⅻ", "2", false, Greater),
+        ];
+        for (a, b, fold_case, expected) in table {
+            assert_eq!(natural_cmp(a, b, fold_case), expected, "{a:?} {b:?} {fold_case}");
+            assert_eq!(natural_cmp(b, a, fold_case), expected.reverse(), "{b:?} {a:?} {fold_case}");
+        }
+    }
+
+    #[test]
+    fn natural_sort_test() {
+        let expected = [
+            "", "0", "00", "1", "01", "1a", "2", "02", "3", "9", "10", "010", "99", "100",
+            "A", "IMG1.png", "a", "a1", "a01", "a2", "a10", "a10b", "a10c",
+            "img1.png", "img2.png", "img02.png", "img10.png", "img١١.png", "img12.png",
+            "x1", "x١", "x１", "x2", "x10", "x10-1", "x10-2", "x10.1",
+        ];
+        let mut input = expected.to_vec();
+        input.reverse();
+        input.rotate_left(7);
+
+        let mut arr = input.clone();
+        merge_sort(&mut arr, natural_lt);
+        assert_eq!(arr, expected);
+
+        let mut arr = input.clone();
+        insert_sort(&mut arr, natural_lt);
+        assert_eq!(arr, expected);
+
+        for algo in registry::lt_sorts::<&str>() {
+            let mut arr = input.clone();
+            (algo.sort)(&mut arr, &mut natural_lt);
+            assert_eq!(arr, expected, "{}", algo.name);
+        }
+
+        // 全序: 与所有两两比较的结果一致
+        for (i, a) in expected.iter().enumerate() {
+            for (j, b) in expected.iter().enumerate() {
+                assert_eq!(natural_cmp(a, b, false), i.cmp(&j), "{a:?} {b:?}");
+            }
+        }
+
+        let mut arr = vec![String::from("b10"), "B2".into(), "a3".into(), "A3".into()];
+        merge_sort(&mut arr, natural_lt_fold_case);
+        assert_eq!(arr, ["A3", "a3", "B2", "b10"]);
+    }
+}